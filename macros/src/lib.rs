@@ -1,22 +1,189 @@
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{LitStr, parse_macro_input};
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, Fields, LitStr, Token, Type, parse_macro_input};
+
+/// Map a gene name's bytes to an "acgt" sequence.
+fn sequence(name: &str) -> String {
+    const SEQ: &[u8; 4] = b"acgt";
+    name.bytes().map(|c| char::from(SEQ[usize::from(c) % 4])).collect()
+}
 
 #[proc_macro]
 pub fn gene(input: TokenStream) -> TokenStream {
     let name = parse_macro_input!(input as LitStr);
-    let mut sequence: Vec<u8> = name.value().into_bytes();
-    for c in sequence.iter_mut() {
-        const SEQ: [u8; 4] = [b'a', b'c', b'g', b't'];
-        *c = SEQ[usize::from(*c) % 4];
-    }
-    let sequence = String::from_utf8(sequence).unwrap();
+    let sequence = sequence(&name.value());
     quote!{
         crate::organism::gene::Gene {
             name: #name,
             sequence: #sequence,
+            mutation: 1.0,
         }
     }.into()
 }
 
+/// Make a `Stat` from a newtype over `f32`.
+///
+/// ```ignore
+/// #[derive(Stat)]
+/// #[stat(gene = "chonkiness", min = 0.7, max = 1.3, default = 1.0, mutation = 0.5)]
+/// pub struct Chonk(f32);
+/// ```
+///
+/// Everything is optional. The gene is named after the type, the range is
+/// 0..1, the default is halfway and `mutation` (which scales the breeding
+/// mutation rate) is 1. `min` can be above `max` for stats that are better
+/// low.
+#[proc_macro_derive(Stat, attributes(stat))]
+pub fn derive_stat(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_stat(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// A number from the attribute, and the tokens to point errors at.
+struct Number {
+    value: f32,
+    tokens: proc_macro2::TokenStream,
+}
+
+#[derive(Default)]
+struct StatArgs {
+    gene: Option<LitStr>,
+    min: Option<Number>,
+    max: Option<Number>,
+    default: Option<Number>,
+    mutation: Option<Number>,
+}
+
+fn parse_number(input: syn::parse::ParseStream) -> syn::Result<Number> {
+    let neg: Option<Token![-]> = input.parse()?;
+    let lit: syn::Lit = input.parse()?;
+    let value = match &lit {
+        syn::Lit::Float(f) => f.base10_parse::<f32>()?,
+        syn::Lit::Int(i) => i.base10_parse::<f32>()?,
+        _ => return Err(syn::Error::new(lit.span(), "expected a number")),
+    };
+    let value = if neg.is_some() { -value } else { value };
+    Ok(Number { value, tokens: quote!(#neg #lit) })
+}
+
+fn parse_args(input: &DeriveInput) -> syn::Result<StatArgs> {
+    let mut args = StatArgs::default();
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("stat")) {
+        attr.parse_nested_meta(|meta| {
+            let key = meta.path.get_ident().map(ToString::to_string).unwrap_or_default();
+            let duplicate = || meta.error(format!("duplicate `{key}`"));
+            match key.as_str() {
+                "gene" => {
+                    if args.gene.is_some() {
+                        return Err(duplicate());
+                    }
+                    args.gene = Some(meta.value()?.parse()?);
+                }
+                "min" | "max" | "default" | "mutation" => {
+                    let slot = match key.as_str() {
+                        "min" => &mut args.min,
+                        "max" => &mut args.max,
+                        "default" => &mut args.default,
+                        _ => &mut args.mutation,
+                    };
+                    if slot.is_some() {
+                        return Err(duplicate());
+                    }
+                    *slot = Some(parse_number(meta.value()?)?);
+                }
+                _ => {
+                    return Err(meta.error(
+                        "unknown stat option, expected `gene`, `min`, `max`, `default` or `mutation`",
+                    ))
+                }
+            }
+            Ok(())
+        })?;
+    }
+    Ok(args)
+}
+
+fn expand_stat(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let ident = &input.ident;
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new(input.generics.span(), "a stat can't be generic"));
+    }
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(ident.span(), "a stat must be a newtype struct like `struct Chonk(f32);`"));
+    };
+    let field = match &data.fields {
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0],
+        Fields::Unit => {
+            return Err(syn::Error::new(ident.span(), "a stat must be a newtype struct like `struct Chonk(f32);`"))
+        }
+        fields => {
+            return Err(syn::Error::new(fields.span(), "a stat must have exactly one unnamed `f32` field"))
+        }
+    };
+    if !matches!(&field.ty, Type::Path(ty) if ty.qself.is_none() && ty.path.is_ident("f32")) {
+        return Err(syn::Error::new(field.ty.span(), "a stat's value must be an `f32`"));
+    }
+
+    let args = parse_args(input)?;
+    let gene = args.gene.unwrap_or_else(|| LitStr::new(&ident.to_string().to_lowercase(), ident.span()));
+    if gene.value().is_empty() {
+        return Err(syn::Error::new(gene.span(), "the gene name can't be empty"));
+    }
+    let min = args.min.as_ref().map_or(0.0, |n| n.value);
+    let max = args.max.as_ref().map_or(1.0, |n| n.value);
+    if min == max {
+        let message = "`min` and `max` can't be the same";
+        return Err(match args.max.as_ref().or(args.min.as_ref()) {
+            Some(n) => syn::Error::new_spanned(&n.tokens, message),
+            None => syn::Error::new(ident.span(), message),
+        });
+    }
+    let default = match &args.default {
+        Some(n) if !(min.min(max)..=min.max(max)).contains(&n.value) => {
+            return Err(syn::Error::new_spanned(&n.tokens, format!("`default` must be between {min} and {max}")));
+        }
+        Some(n) => n.value,
+        None => (min + max) / 2.0,
+    };
+    let mutation = match &args.mutation {
+        Some(n) if n.value < 0.0 => {
+            return Err(syn::Error::new_spanned(&n.tokens, "`mutation` can't be negative"));
+        }
+        Some(n) => n.value,
+        None => 1.0,
+    };
+
+    let sequence = sequence(&gene.value());
+    Ok(quote! {
+        impl crate::organism::gene::Stat for #ident {
+            const GENE: &'static crate::organism::gene::Gene<'static> = &crate::organism::gene::Gene {
+                name: #gene,
+                sequence: #sequence,
+                mutation: #mutation,
+            };
+            const MIN: f32 = #min;
+            const MAX: f32 = #max;
+            const DEFAULT: f32 = #default;
+
+            fn from_value(value: f32) -> Self {
+                Self(value)
+            }
+            fn value(&self) -> f32 {
+                self.0
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn test_sequence() {
+        assert_eq!(super::sequence("size"), "tcgc");
+        assert_eq!(super::sequence(""), "");
+    }
+}
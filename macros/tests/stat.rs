@@ -0,0 +1,39 @@
+#[path = "support/organism.rs"]
+mod organism;
+
+use macros::{Stat, gene};
+use organism::gene::Stat;
+
+#[derive(Stat)]
+#[stat(gene = "chonkiness", min = 0.7, max = 1.3, default = 1.0, mutation = 0.5)]
+struct Chonk(f32);
+
+#[derive(Stat)]
+struct Zoom(f32);
+
+#[derive(Stat)]
+#[stat(min = -1, max = -3)]
+#[stat(default = -2.5)]
+struct Grumpy(f32);
+
+#[test]
+fn test_stat_attributes() {
+    assert_eq!(Chonk::GENE.name, "chonkiness");
+    assert_eq!(Chonk::GENE.sequence, gene!("chonkiness").sequence);
+    assert_eq!(Chonk::GENE.mutation, 0.5);
+    assert_eq!((Chonk::MIN, Chonk::MAX, Chonk::DEFAULT), (0.7, 1.3, 1.0));
+    assert_eq!(Chonk::from_value(1.2).value(), 1.2);
+}
+
+#[test]
+fn test_stat_defaults() {
+    assert_eq!(Zoom::GENE.name, "zoom");
+    assert_eq!(Zoom::GENE.sequence.len(), 4);
+    assert_eq!(Zoom::GENE.mutation, 1.0);
+    assert_eq!((Zoom::MIN, Zoom::MAX, Zoom::DEFAULT), (0.0, 1.0, 0.5));
+}
+
+#[test]
+fn test_stat_negative_range() {
+    assert_eq!((Grumpy::MIN, Grumpy::MAX, Grumpy::DEFAULT), (-1.0, -3.0, -2.5));
+}
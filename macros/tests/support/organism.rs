@@ -0,0 +1,19 @@
+//! Stand-in for the game's `organism` module, so the macros can be tested
+//! on their own.
+pub mod gene {
+    pub struct Gene<'a> {
+        pub name: &'a str,
+        pub sequence: &'a str,
+        pub mutation: f32,
+    }
+
+    pub trait Stat {
+        const GENE: &'static Gene<'static>;
+        const MIN: f32;
+        const MAX: f32;
+        const DEFAULT: f32;
+
+        fn from_value(value: f32) -> Self where Self: Sized;
+        fn value(&self) -> f32;
+    }
+}
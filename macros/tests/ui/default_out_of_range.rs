@@ -0,0 +1,10 @@
+#[path = "../support/organism.rs"]
+mod organism;
+
+use macros::Stat;
+
+#[derive(Stat)]
+#[stat(min = 0.7, max = 1.3, default = 2.0)]
+struct Chonk(f32);
+
+fn main() {}
@@ -0,0 +1,10 @@
+#[path = "../support/organism.rs"]
+mod organism;
+
+use macros::Stat;
+
+#[derive(Stat)]
+#[stat(min = 0.5, min = 0.7)]
+struct Chonk(f32);
+
+fn main() {}
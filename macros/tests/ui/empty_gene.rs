@@ -0,0 +1,10 @@
+#[path = "../support/organism.rs"]
+mod organism;
+
+use macros::Stat;
+
+#[derive(Stat)]
+#[stat(gene = "")]
+struct Chonk(f32);
+
+fn main() {}
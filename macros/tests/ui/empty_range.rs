@@ -0,0 +1,10 @@
+#[path = "../support/organism.rs"]
+mod organism;
+
+use macros::Stat;
+
+#[derive(Stat)]
+#[stat(min = 1.0, max = 1.0)]
+struct Chonk(f32);
+
+fn main() {}
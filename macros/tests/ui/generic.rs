@@ -0,0 +1,9 @@
+#[path = "../support/organism.rs"]
+mod organism;
+
+use macros::Stat;
+
+#[derive(Stat)]
+struct Chonk<T>(f32, std::marker::PhantomData<T>);
+
+fn main() {}
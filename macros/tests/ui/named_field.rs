@@ -0,0 +1,11 @@
+#[path = "../support/organism.rs"]
+mod organism;
+
+use macros::Stat;
+
+#[derive(Stat)]
+struct Chonk {
+    size: f32,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+#[path = "../support/organism.rs"]
+mod organism;
+
+use macros::Stat;
+
+#[derive(Stat)]
+#[stat(mutation = -0.5)]
+struct Chonk(f32);
+
+fn main() {}
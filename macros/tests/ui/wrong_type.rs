@@ -0,0 +1,9 @@
+#[path = "../support/organism.rs"]
+mod organism;
+
+use macros::Stat;
+
+#[derive(Stat)]
+struct Chonk(u8);
+
+fn main() {}
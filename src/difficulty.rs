@@ -0,0 +1,85 @@
+//! Difficulty presets. Each preset scales the base [`GameSettings`].
+use serde::Deserialize;
+
+use crate::prelude::*;
+use crate::settings::GameSettings;
+
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+impl Difficulty {
+    const ALL: &'static [Self] = &[Self::Easy, Self::Normal, Self::Hard];
+
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Easy => "EASY",
+            Self::Normal => "NORMAL",
+            Self::Hard => "HARD",
+        }
+    }
+    #[must_use]
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|d| *d == self).unwrap();
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+    #[must_use]
+    pub fn prev(self) -> Self {
+        let i = Self::ALL.iter().position(|d| *d == self).unwrap();
+        Self::ALL[(i + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+/// How a difficulty changes the base settings.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct DifficultyProfile {
+    pub egg_spawn_time_scale: f32, // > 1 means eggs spawn less often
+    pub egg_spawn_speedup_scale: f32,
+    pub aging_scale: f32,
+    pub speed_ramp_scale: f32,
+    pub starting_dirt: Option<u32>, // None is unlimited dirt
+    pub fight_on_chance: f32, // 0..1 chance a beaten bee fights on instead of retreating
+}
+impl Default for DifficultyProfile {
+    fn default() -> Self {
+        Self {
+            egg_spawn_time_scale: 1.0,
+            egg_spawn_speedup_scale: 1.0,
+            aging_scale: 1.0,
+            speed_ramp_scale: 1.0,
+            starting_dirt: None,
+            fight_on_chance: 1.0,
+        }
+    }
+}
+
+impl GameSettings {
+    #[must_use]
+    pub fn profile(&self, difficulty: Difficulty) -> &DifficultyProfile {
+        match difficulty {
+            Difficulty::Easy => &self.easy,
+            Difficulty::Normal => &self.normal,
+            Difficulty::Hard => &self.hard,
+        }
+    }
+
+    /// The settings to play with at the given difficulty.
+    #[must_use]
+    pub fn with_difficulty(&self, difficulty: Difficulty) -> Self {
+        let profile = self.profile(difficulty);
+        let mut settings = self.clone();
+        settings.egg_spawn_time_start *= profile.egg_spawn_time_scale;
+        settings.egg_spawn_time_end *= profile.egg_spawn_time_scale;
+        settings.egg_spawn_speedup_perc *= profile.egg_spawn_speedup_scale;
+        settings.aging_amount_per_tick *= profile.aging_scale;
+        settings.rumblebee_per_egg_speedup_perc *= profile.speed_ramp_scale;
+        settings.starting_dirt = profile.starting_dirt;
+        settings.fight_on_chance = profile.fight_on_chance.clamp(0.0, 1.0);
+        settings
+    }
+}
@@ -0,0 +1,117 @@
+//! How factions get along. Hostile factions fight, and allies collect each
+//! other's eggs. The level sets the starting table, and it can change in
+//! play: with `ally_against_leader`, everyone else teams up against a
+//! faction that gets too far ahead.
+use bevy::utils::HashMap;
+use serde::Deserialize;
+
+use crate::prelude::*;
+use crate::rumblebees::RumbleBee;
+use crate::settings::GameSettings;
+use crate::simulation::SimSet;
+use crate::terrain::Faction;
+use crate::GameState;
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Relation {
+    Allied, // Don't fight, and collect each other's eggs
+    Neutral, // Don't fight
+    Hostile,
+}
+
+#[derive(Resource, Debug, Default, Clone)]
+pub struct Diplomacy {
+    relations: HashMap<(Faction, Faction), Relation>,
+    /// The faction everyone has ganged up on, if any.
+    pub leader: Option<Faction>,
+}
+impl Diplomacy {
+    /// Same faction is allied, unlisted pairs are hostile.
+    #[must_use]
+    pub fn relation(&self, a: Faction, b: Faction) -> Relation {
+        if a == b {
+            return Relation::Allied;
+        }
+        self.relations.get(&(a, b)).copied().unwrap_or(Relation::Hostile)
+    }
+    pub fn set(&mut self, a: Faction, b: Faction, relation: Relation) {
+        self.relations.insert((a, b), relation);
+        self.relations.insert((b, a), relation);
+    }
+    #[must_use]
+    pub fn will_fight(&self, a: Faction, b: Faction) -> bool {
+        self.relation(a, b) == Relation::Hostile
+    }
+    /// Green eggs are for everyone.
+    #[must_use]
+    pub fn can_collect(&self, bee: Faction, egg: Faction) -> bool {
+        egg == Faction::Green || self.relation(bee, egg) == Relation::Allied
+    }
+    #[must_use]
+    pub fn from_rules(relations: &[(Faction, Faction, Relation)]) -> Self {
+        let mut diplomacy = Self::default();
+        for (a, b, relation) in relations {
+            diplomacy.set(*a, *b, *relation);
+        }
+        diplomacy
+    }
+}
+
+pub struct DiplomacyPlugin;
+impl Plugin for DiplomacyPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Diplomacy>()
+            .add_systems(OnEnter(GameState::InGame), diplomacy_setup)
+            .add_systems(FixedUpdate, ally_against_leader.in_set(SimSet::Simulate));
+    }
+}
+
+fn diplomacy_setup(
+    mut commands: Commands,
+    settings: Res<GameSettings>,
+) {
+    commands.insert_resource(Diplomacy::from_rules(&settings.level.relations));
+}
+
+/// Everyone else allies against a faction with `ratio` times the bees of
+/// the next biggest. The level's table comes back once it's cut down.
+fn ally_against_leader(
+    beez: Query<&RumbleBee>,
+    mut diplomacy: ResMut<Diplomacy>,
+    settings: Res<GameSettings>,
+) {
+    let Some(ratio) = settings.level.ally_against_leader else {
+        return;
+    };
+    let factions = &settings.level.factions;
+    if factions.len() < 3 {
+        return;
+    }
+    let mut counts: Vec<(Faction, usize)> = factions
+        .iter()
+        .map(|faction| (*faction, beez.iter().filter(|bee| bee.faction == *faction).count()))
+        .collect();
+    counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    let (top, top_count) = counts[0];
+    let dominant = top_count as f32 > counts[1].1.max(1) as f32 * ratio;
+
+    let leader = dominant.then_some(top);
+    if leader == diplomacy.leader {
+        return;
+    }
+
+    let mut updated = Diplomacy::from_rules(&settings.level.relations);
+    if let Some(leader) = leader {
+        for a in factions.iter().filter(|f| **f != leader) {
+            for b in factions.iter().filter(|f| **f != leader && *f != a) {
+                updated.set(*a, *b, Relation::Allied);
+            }
+        }
+        screen_print!(col: leader.color(), "EVERYONE AGAINST {:?}!", leader);
+    } else {
+        screen_print!("The alliance breaks up");
+    }
+    updated.leader = leader;
+    *diplomacy = updated;
+}
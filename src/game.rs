@@ -1,11 +1,12 @@
 use std::ops::{Add, Sub};
 use crate::pathfinding::{Pathfinding, Navmesh};
 use crate::pointer::Pointer;
-use crate::settings::{RUMBLEBEE_SPEED_START, DIG_REPEAT_IN_SECS, DIG_POWER, EGG_SPAWN_TIME_START};
-use crate::terrain::{GAP_LEFT, px_to_tilepos};
+use crate::rumblebees::BeeStats;
+use crate::difficulty::Difficulty;
+use crate::settings::GameSettings;
+use crate::simulation::{SimPosition, SimSet, interpolate_positions, sim_delta};
+use crate::terrain::{Faction, GAP_LEFT, px_to_tilepos};
 use crate::{despawn_screen, GameState, AssetCol};
-use bevy::math::Vec3Swizzles;
-use bevy::utils::Instant;
 use bevy::{prelude::*, window::PrimaryWindow};
 use bevy_kira_audio::prelude::*;
 
@@ -25,17 +26,23 @@ impl Plugin for GamePlugin {
                 (
                     bevy::window::close_on_esc,
                     check_exit,
-                    move_bob,
-                    follow_path,
-                    check_if_stuck_in_tile,
-                    smash_dirt_when_stuck,
+                    move_bob.after(interpolate_positions),
                     animate_sprite,
+                    animate_images,
                     update_sprite,
                     egg_listener,
                     game_over
                 )
                     .run_if(in_state(GameState::InGame)),
             )
+            .add_systems(
+                FixedUpdate,
+                (
+                    follow_path,
+                    check_if_stuck_in_tile,
+                    smash_dirt_when_stuck,
+                ).in_set(SimSet::Simulate),
+            )
             .add_systems(OnExit(GameState::InGame), despawn_screen::<OnGameScreen>);
     }
 }
@@ -49,7 +56,7 @@ pub struct FollowPath {
 #[derive(Component)]
 pub struct Stuck {
     tile: Entity,
-    last_dig: Instant
+    dig_timer: Timer
 }
 
 #[derive(Component)]
@@ -81,6 +88,11 @@ pub struct Health(pub f32);
 #[derive(Component)]
 pub struct Bob;
 
+/// A child that stays the right way round when its parent turns to face
+/// left, like text.
+#[derive(Component)]
+pub struct Upright;
+
 #[derive(Component)]
 pub struct Wander;
 
@@ -101,13 +113,21 @@ pub struct AnimationIndices {
 #[derive(Component, Deref, DerefMut)]
 pub struct AnimationTimer(pub Timer);
 
+/// Like `AnimationIndices`, for sprites drawn from separate images.
+#[derive(Component)]
+pub struct ImageFrames {
+    pub frames: Vec<Handle<Image>>,
+    pub cur: usize
+}
+
 #[derive(Resource)]
 pub struct GameData {
     pub eggs_spawned: usize,
     pub game_over: bool,
     pub bee_base_speed: f32,
-    pub egg_spawn_time: f32
-
+    pub egg_spawn_time: f32,
+    pub difficulty: Difficulty, // Recorded with the score
+    pub eliminated: Vec<Faction>, // Factions with no bees left
 }
 
 #[derive(Event, Default)]
@@ -130,7 +150,9 @@ fn egg_listener(
 
 }
 
+/// Bob up and down. Applied on top of the interpolated position each frame.
 fn move_bob(time: Res<Time>, mut pos: Query<(&mut Transform, Option<&Displacement>, With<Bob>)>) {
+    const BOB_HEIGHT: f32 = 2.5;
     let mut i = 0.;
     for (mut transform, displacment, _bob) in &mut pos {
         let mut do_bob = true;
@@ -142,19 +164,29 @@ fn move_bob(time: Res<Time>, mut pos: Query<(&mut Transform, Option<&Displacemen
         }
         if do_bob {
            transform.translation.y +=
-                    ((time.elapsed_seconds() + i) * 10.0).sin() * 0.4;
+                    ((time.elapsed_seconds() + i) * 10.0).sin() * BOB_HEIGHT;
         }
     }
 }
 
 fn update_sprite(
-    mut query: Query<(&mut Transform, Option<&Displacement>)>
+    mut query: Query<(&mut Transform, Option<&Displacement>, Option<&Children>), Without<Upright>>,
+    mut upright: Query<&mut Transform, With<Upright>>,
 ) {
-    for (mut transform, displacement) in query.iter_mut() {
+    for (mut transform, displacement, kids) in query.iter_mut() {
         // Face the direction you are moving.
         if let Some(displacement) = displacement {
             if displacement.0.x != 0.0 {
-                transform.scale.x = transform.scale.x.abs() * if displacement.0.x < 0.0 { -1.0 } else { 1.0 };
+                let facing = if displacement.0.x < 0.0 { -1.0 } else { 1.0 };
+                transform.scale.x = transform.scale.x.abs() * facing;
+                // Flip upright kids back
+                if let Some(kids) = kids {
+                    for &kid in kids.iter() {
+                        if let Ok(mut kid) = upright.get_mut(kid) {
+                            kid.scale.x = kid.scale.x.abs() * facing;
+                        }
+                    }
+                }
             }
         }
         // TODO: clamp position inside screen (stop Bob from pushing to outside tile)
@@ -178,10 +210,25 @@ fn animate_sprite(
     }
 }
 
+fn animate_images(
+    time: Res<Time>,
+    mut query: Query<(&mut ImageFrames, &mut AnimationTimer, &mut Handle<Image>)>,
+) {
+    for (mut frames, mut timer, mut image) in &mut query {
+        timer.tick(time.delta());
+        if timer.just_finished() {
+            frames.cur = (frames.cur + 1) % frames.frames.len();
+            *image = frames.frames[frames.cur].clone();
+        }
+    }
+}
+
 fn game_setup(
     mut commands: Commands,
     window_query: Query<&Window, With<PrimaryWindow>>,
     assets: Res<AssetCol>,
+    settings: Res<GameSettings>,
+    difficulty: Res<Difficulty>,
     _audio: Res<Audio>
 ) {
     let window: &Window = window_query.get_single().unwrap();
@@ -189,8 +236,10 @@ fn game_setup(
     commands.insert_resource(GameData {
         eggs_spawned: 0,
         game_over: false,
-        bee_base_speed: RUMBLEBEE_SPEED_START,
-        egg_spawn_time: EGG_SPAWN_TIME_START
+        bee_base_speed: settings.rumblebee_speed_start,
+        egg_spawn_time: settings.egg_spawn_time_start,
+        difficulty: *difficulty,
+        eliminated: vec![],
     });
 
     /*audio
@@ -282,35 +331,44 @@ fn check_exit(
 }
 
 pub fn follow_path(
-    time: Res<Time>,
+    fixed_time: Res<FixedTime>,
     mut commands: Commands,
     mut query: Query<
-            (Entity, &mut Pathfinding, &mut Transform, &Speed, Option<&mut Displacement>),
+            (Entity, &mut Pathfinding, &mut SimPosition, &Speed, Option<&mut Displacement>),
         (With<FollowPath>, Without<Stuck>)>,
     tilemap: Query<(
         &TilemapGridSize,
         &TilemapType,
+        &TileStorage,
     )>,
+    tiles: Query<&Tile>,
+    settings: Res<GameSettings>,
 ) {
     /// Distance to the target considered "at" the target.
     const TARGET_EPSILON: f32 = 5.0;
-    let (grid_size, map_type) = tilemap.single();
-    let delta_time = time.delta_seconds().min(0.3);
-    if delta_time > 0.2 {
-        info!(">>> dt");
-    }
-    for (entity, mut path, mut transform, speed, displacement) in &mut query {
+    let (grid_size, map_type, storage) = tilemap.single();
+    let delta_time = sim_delta(&fixed_time);
+    for (entity, mut path, mut sim, speed, displacement) in &mut query {
         //TODO: get size from entity
         let target = path.current(grid_size, map_type).add(Vec2 { x: GAP_LEFT + 25., y: 25. });
 
+        // Slow going through leaves
+        let tile_pos = px_to_tilepos(sim.pos.add(Vec2 { x: -GAP_LEFT, y: 0.0 }), grid_size);
+        let in_leaves = storage.get(&tile_pos)
+            .and_then(|e| tiles.get(e).ok())
+            .is_some_and(|tile| matches!(tile, Tile::Leaves { .. }));
+        let speed = if in_leaves { speed.speed * settings.leaves_speed_scale } else { speed.speed };
+
+        // Never step past the target
+        let to_target = target.sub(sim.pos);
         let delta =
-            target.sub(transform.translation.xy()).normalize() * delta_time * speed.speed;
+            (to_target.normalize_or_zero() * delta_time * speed).clamp_length_max(to_target.length());
 
         if let Some(mut displacement) = displacement {
             displacement.0 = delta;
         }
-        transform.translation += delta.extend(0.0);
-        if transform.translation.xy().distance(target) < TARGET_EPSILON && !path.step() {
+        sim.pos += delta;
+        if sim.pos.distance(target) < TARGET_EPSILON && !path.step() {
             commands.entity(entity).remove::<Pathfinding>();
         }
     }
@@ -323,19 +381,8 @@ pub fn update_navmesh_on_tile_change(
 ) {
     for (tile, tile_pos) in &mut tile_query {
         let mut nmp = navmesh.get_single_mut().unwrap();
-        nmp.main.set_solid(*tile_pos, match tile {
-            // TODO: this logic is repeated several times!
-            Tile::Air => false,
-            Tile::Egg { .. } => false,
-            _ => true
-        });
-        nmp.alt.set_solid(*tile_pos, match tile {
-            // TODO: this logic is repeated several times!
-            Tile::Air => false,
-            Tile::Egg { .. } => false,
-            Tile::Dirt { .. } => false,
-            _ => true
-        });
+        nmp.main.set_solid(*tile_pos, Tile::is_solid(*tile));
+        nmp.alt.set_solid(*tile_pos, Tile::is_solid_for_digger(*tile));
     }
 }
 
@@ -360,19 +407,19 @@ pub fn remove_conflicting_paths_on_tile_change(
 
 fn check_if_stuck_in_tile(
     mut commands: Commands,
-    mut query: Query<(Entity, &Transform), (With<Pathfinding>, Without<Stuck>)>,
+    mut query: Query<(Entity, &SimPosition), (With<Pathfinding>, Without<Stuck>)>,
     tilemap: Query<(
         &TilemapGridSize,
         &TileStorage,
     )>,
     tiles: Query<&Tile, With<HealthByte>>,
-    time: Res<Time>
+    settings: Res<GameSettings>,
 ){
     let (grid_size, storage) = tilemap.single();
 
-    for (entity, transform) in &mut query {
+    for (entity, sim) in &mut query {
         // Am I currently inside a solid block?
-        let pos = transform.translation.xy().add(Vec2 { x: -GAP_LEFT, y: 0.0 });
+        let pos = sim.pos.add(Vec2 { x: -GAP_LEFT, y: 0.0 });
         let tile_pos = px_to_tilepos(pos, grid_size);
         let tile_ent = storage.get(&tile_pos);
         let tile = tile_ent.and_then(|e| tiles.get(e).ok());
@@ -381,7 +428,7 @@ fn check_if_stuck_in_tile(
             commands.entity(entity)
                 .insert(Stuck {
                     tile: tile_ent.unwrap(),
-                    last_dig: time.last_update().unwrap() } )
+                    dig_timer: Timer::from_seconds(settings.dig_repeat_in_secs, TimerMode::Repeating) } )
                 .remove::<Pathfinding>();
         }
     }
@@ -390,29 +437,25 @@ fn check_if_stuck_in_tile(
 /// When entity is stuck in dirt, smash out after some time
 fn smash_dirt_when_stuck(
     mut commands: Commands,
-    mut ents: Query<(Entity, &mut Stuck)>,
-    mut tiles: Query<(&mut Tile, &mut HealthByte, &mut TileColor)>,
-    time: Res<Time>
+    mut ents: Query<(Entity, &mut Stuck, Option<&BeeStats>)>,
+    mut tiles: Query<(&mut Tile, &mut HealthByte)>,
+    fixed_time: Res<FixedTime>,
+    settings: Res<GameSettings>
 ) {
 
-    for (entity, mut stuck) in ents.iter_mut() {
-        let t = time.last_update().unwrap();
-        let dt = t - stuck.last_dig;
-        if dt.as_secs_f32() < DIG_REPEAT_IN_SECS {
+    for (entity, mut stuck, stats) in ents.iter_mut() {
+        if !stuck.dig_timer.tick(fixed_time.period).just_finished() {
             continue;
         }
-        stuck.last_dig = t;
 
         let mut tile_done = false;
-        if let Ok((mut tile, mut health, mut color)) = tiles.get_mut(stuck.tile) {
-            health.0 = health.0.saturating_sub(DIG_POWER); // Kill some dirt HP.
-            let hp = health.0;
-            if hp == 0 {
+        if let Ok((mut tile, mut health)) = tiles.get_mut(stuck.tile) {
+            let dig_power = stats.map_or(settings.dig_power, |stats| stats.dig_power);
+            // Kill some dirt HP. The tint follows it (soil.rs)
+            health.0 = health.0.saturating_sub(dig_power);
+            if health.0 == 0 {
                 *tile = Tile::Air;
                 tile_done = true;
-                color.0.set_a(100.);
-            } else {
-                color.0.set_a(hp as f32 / 100.);
             }
         } else {
             tile_done = true;
@@ -427,7 +470,8 @@ fn smash_dirt_when_stuck(
 fn game_over(
     mut commands: Commands,
     go: Query<Ref<GameOver>>,
-    assets: Res<AssetCol>
+    assets: Res<AssetCol>,
+    game_data: Res<GameData>
 ) {
     for go in go.iter() {
         if go.is_added() {
@@ -470,6 +514,26 @@ fn game_over(
                         ..default()
                     }),
             ));
+
+            commands.spawn((
+                OnGameScreen,
+                TextBundle::from_section(
+                    format!("{} EGGS ON {}", game_data.eggs_spawned, game_data.difficulty.label()),
+                    TextStyle {
+                        font: assets.font.clone(),
+                        font_size: 40.0,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                )
+                    .with_text_alignment(TextAlignment::Center)
+                    .with_style(Style {
+                        position_type: PositionType::Absolute,
+                        left: Val::Px(260.0),
+                        top: Val::Px(360.0),
+                        ..default()
+                    }),
+            ));
         }
     }
 
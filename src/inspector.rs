@@ -0,0 +1,154 @@
+//! Bee inspector. Hover over a bee to see who they are and what they've
+//! been up to. Press I over a bee to keep the panel on them, and again to
+//! let go.
+use crate::game::{Health, OnGameScreen, Speed};
+use crate::organism::gene::BEE_GENES;
+use crate::organism::genome::Genome;
+use crate::pointer::Pointer;
+use crate::prelude::*;
+use crate::rumblebees::behaviour::Decision;
+use crate::rumblebees::life::LifeStage;
+use crate::rumblebees::needs::Hunger;
+use crate::rumblebees::{BeeRecord, BeeStats, RumbleBee};
+use crate::simulation::SimPosition;
+use crate::{AssetCol, GameState};
+
+const HOVER_RADIUS: f32 = 25.0;
+
+#[derive(Resource, Debug, Default)]
+pub struct Inspected {
+    pub hovered: Option<Entity>,
+    pub pinned: Option<Entity>,
+}
+impl Inspected {
+    /// The bee to show: the pinned one, otherwise the one under the pointer.
+    #[must_use]
+    pub fn bee(&self) -> Option<Entity> {
+        self.pinned.or(self.hovered)
+    }
+}
+
+#[derive(Component)]
+struct InspectorPanel;
+
+pub struct InspectorPlugin;
+impl Plugin for InspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Inspected>()
+            .add_systems(OnEnter(GameState::InGame), inspector_setup)
+            .add_systems(
+                Update,
+                (
+                    hover_bees,
+                    pin_bee.after(hover_bees),
+                    update_inspector.after(pin_bee),
+                ).run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+fn inspector_setup(
+    mut commands: Commands,
+    assets: Res<AssetCol>,
+) {
+    commands.insert_resource(Inspected::default());
+
+    let mut panel = TextBundle::from_section(
+        "",
+        TextStyle {
+            font: assets.font.clone(),
+            font_size: 16.0,
+            color: Color::WHITE,
+        },
+    )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            right: Val::Px(15.0),
+            top: Val::Px(15.0),
+            padding: UiRect::all(Val::Px(8.0)),
+            display: Display::None,
+            ..default()
+        });
+    panel.background_color = BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.7));
+    commands.spawn((panel, InspectorPanel, OnGameScreen));
+}
+
+fn hover_bees(
+    beez: Query<(Entity, &SimPosition), With<RumbleBee>>,
+    pointer: Res<Pointer>,
+    mut inspected: ResMut<Inspected>,
+) {
+    if inspected.pinned.is_some_and(|bee| !beez.contains(bee)) {
+        // Passed away
+        inspected.pinned = None;
+    }
+    inspected.hovered = beez
+        .iter()
+        .map(|(ent, sim)| (ent, sim.pos.distance(pointer.pos)))
+        .filter(|(_, dist)| *dist < HOVER_RADIUS)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(ent, _)| ent);
+}
+
+fn pin_bee(
+    keys: Res<Input<KeyCode>>,
+    mut inspected: ResMut<Inspected>,
+) {
+    if keys.just_pressed(KeyCode::I) {
+        inspected.pinned = match inspected.hovered {
+            Some(bee) if inspected.pinned != Some(bee) => Some(bee),
+            _ => None,
+        };
+    }
+}
+
+fn update_inspector(
+    mut panel: Query<(&mut Text, &mut Style), With<InspectorPanel>>,
+    beez: Query<(
+        &Name, &RumbleBee, &LifeStage, &Health, &Hunger, &BeeStats, &Speed, &Genome, &BeeRecord,
+        &Decision,
+    )>,
+    inspected: Res<Inspected>,
+) {
+    let Ok((mut text, mut style)) = panel.get_single_mut() else { return };
+    let Some((name, bee, stage, health, hunger, stats, speed, genome, record, decision)) =
+        inspected.bee().and_then(|ent| beez.get(ent).ok())
+    else {
+        style.display = Display::None;
+        return;
+    };
+    style.display = Display::Flex;
+
+    let mut info = format!(
+        "{}{}\n{:?} {:?}  AGE {:.0}s\nHEALTH {:.0}/{:.0}  HUNGER {:.0}\nSPEED {:.0}\nPARENT {}\nKILLS {}  EGGS {}\n",
+        name,
+        if inspected.pinned.is_some() { " (I)" } else { "" },
+        bee.faction,
+        stage,
+        record.age,
+        health.0.max(0.0),
+        stats.max_health,
+        hunger.0,
+        speed.speed,
+        record.parent.as_deref().unwrap_or("-"),
+        record.kills,
+        record.eggs,
+    );
+    // Runners-up too, to see how close the call was
+    for (behaviour, score) in decision.scores.iter().take(3) {
+        info += &format!("\n{:<10} {:.2}", behaviour.to_uppercase(), score);
+    }
+    info.push('\n');
+    for gene in BEE_GENES {
+        let Some(bases) = genome.bases(gene) else { continue };
+        let level = genome.level(gene).unwrap_or(0.5);
+        info += &format!(
+            "\n{:<10} {} {:.2}",
+            gene.name,
+            String::from_utf8_lossy(bases),
+            level,
+        );
+    }
+    text.sections[0].value = info;
+}
@@ -2,13 +2,15 @@ use crate::AssetCol;
 use crate::game::GameData;
 use crate::game::OnGameScreen;
 use crate::prelude::*;
+use crate::rumblebees::RumbleBee;
+use crate::settings::GameSettings;
+use crate::terrain::Faction;
 use crate::GameState;
 
-pub const DIRT_AMOUNT:u32 = 64;
-
 #[derive(Resource, Default, Debug)]
 pub struct Inventory {
     pub dirt: u32,
+    pub unlimited: bool,
 }
 
 pub struct UIPlugin;
@@ -18,7 +20,7 @@ impl Plugin for UIPlugin {
             .add_systems(OnEnter(GameState::InGame), ui_setup)
             .add_systems(
                 Update,
-                update_ui.run_if(in_state(GameState::InGame)),
+                (update_ui, update_difficulty_ui, update_faction_ui).run_if(in_state(GameState::InGame)),
             );
     }
 }
@@ -26,16 +28,27 @@ impl Plugin for UIPlugin {
 #[derive(Component)]
 struct UIDirtAmount;
 
+#[derive(Component)]
+struct UIDifficulty;
+
+/// Bees left in a faction.
+#[derive(Component)]
+struct UIFactionCount(Faction);
+
 fn ui_setup(
     mut commands: Commands,
-    assets: Res<AssetCol>
+    assets: Res<AssetCol>,
+    settings: Res<GameSettings>,
 ) {
-    commands.insert_resource(Inventory { dirt: DIRT_AMOUNT });
+    commands.insert_resource(Inventory {
+        dirt: settings.starting_dirt.unwrap_or(0),
+        unlimited: settings.starting_dirt.is_none(),
+    });
 
     commands.spawn((
         OnGameScreen,
         TextBundle::from_section(
-            DIRT_AMOUNT.to_string(),
+            "0",
             TextStyle {
                 font: assets.font.clone(),
                 font_size: 40.0,
@@ -55,7 +68,7 @@ fn ui_setup(
     commands.spawn((
         OnGameScreen,
         TextBundle::from_section(
-            DIRT_AMOUNT.to_string(),
+            "0",
             TextStyle {
                 font: assets.font.clone(),
                 font_size: 40.0,
@@ -72,6 +85,46 @@ fn ui_setup(
             }),
         UIDirtAmount));
 
+    commands.spawn((
+        OnGameScreen,
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: assets.font.clone(),
+                font_size: 16.0,
+                color: Color::WHITE,
+                ..default()
+            },
+        )
+            .with_text_alignment(TextAlignment::Center)
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                left: Val::Px(15.0),
+                top: Val::Px(60.0),
+                ..default()
+            }),
+        UIDifficulty));
+
+    for (i, faction) in settings.level.factions.iter().enumerate() {
+        commands.spawn((
+            OnGameScreen,
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font: assets.font.clone(),
+                    font_size: 16.0,
+                    color: faction.color(),
+                    ..default()
+                },
+            )
+                .with_style(Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(15.0),
+                    top: Val::Px(110.0 + i as f32 * 22.0),
+                    ..default()
+                }),
+            UIFactionCount(*faction)));
+    }
 }
 
 fn update_ui(
@@ -82,3 +135,33 @@ fn update_ui(
         text.sections[0].value = format!("{}", game_data.eggs_spawned);
     }
 }
+
+fn update_difficulty_ui(
+    mut ui_difficulty: Query<&mut Text, With<UIDifficulty>>,
+    game_data: Res<GameData>,
+    inv: Res<Inventory>,
+) {
+    for mut text in &mut ui_difficulty {
+        text.sections[0].value = if inv.unlimited {
+            game_data.difficulty.label().to_string()
+        } else {
+            format!("{}\nDIRT {}", game_data.difficulty.label(), inv.dirt)
+        };
+    }
+}
+
+fn update_faction_ui(
+    mut ui_factions: Query<(&mut Text, &UIFactionCount)>,
+    beez: Query<&RumbleBee>,
+    game_data: Res<GameData>,
+) {
+    for (mut text, UIFactionCount(faction)) in &mut ui_factions {
+        let label = format!("{:?}", faction).to_uppercase();
+        text.sections[0].value = if game_data.eliminated.contains(faction) {
+            format!("{} X", label)
+        } else {
+            let count = beez.iter().filter(|bee| bee.faction == *faction).count();
+            format!("{} {}", label, count)
+        };
+    }
+}
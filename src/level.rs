@@ -0,0 +1,29 @@
+//! Rules for the level being played. Set in the `level` section of
+//! `assets/settings.ron`.
+use serde::Deserialize;
+
+use crate::diplomacy::Relation;
+use crate::terrain::{EggFactionMode, EggSpawnMode, Faction};
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct LevelRules {
+    pub factions: Vec<Faction>, // The teams at war. The last one left wins
+    pub relations: Vec<(Faction, Faction, Relation)>, // Unlisted pairs are hostile
+    pub ally_against_leader: Option<f32>, // Gang up on a faction this many times bigger than the next
+    pub egg_faction_mode: EggFactionMode,
+    pub egg_spawn_mode: EggSpawnMode,
+    pub falling_tiles: bool, // Loose dirt, poo and eggs fall
+}
+impl Default for LevelRules {
+    fn default() -> Self {
+        Self {
+            factions: vec![Faction::Red, Faction::Blue],
+            relations: vec![],
+            ally_against_leader: None,
+            egg_faction_mode: EggFactionMode::default(),
+            egg_spawn_mode: EggSpawnMode::default(),
+            falling_tiles: false,
+        }
+    }
+}
@@ -0,0 +1,437 @@
+//! Family trees. Every bee born in a game is recorded with their parents,
+//! and kept after they die. Press L to show the inspected bee's family,
+//! each faction's most prolific bee and which genes are spreading, and X to
+//! export the lot as JSON and Graphviz DOT.
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use crate::game::OnGameScreen;
+use crate::inspector::Inspected;
+use crate::organism::gene::BEE_GENES;
+use crate::organism::genome::Genome;
+use crate::prelude::*;
+use crate::rumblebees::{BeeKilled, BeeRecord};
+use crate::settings::GameSettings;
+use crate::simulation::SimSet;
+use crate::terrain::Faction;
+use crate::{AssetCol, GameState};
+
+const ANCESTOR_DEPTH: usize = 3;
+const EXPORT_NAME: &str = "lineage";
+
+/// Where a bee is in the `Lineage`.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub struct LineageId(pub usize);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LineageEntry {
+    pub id: usize,
+    pub name: String,
+    pub faction: Faction,
+    pub parents: Vec<usize>, // The collector, then the mate if there was one
+    pub children: Vec<usize>, // In birth order
+    pub generation: u32, // 0 for the first bees
+    pub genes: Vec<(&'static str, f32)>, // Gene levels
+    pub alive: bool,
+    pub age: f32,
+    pub kills: u32,
+    pub eggs: u32,
+}
+
+/// Every bee this game, in birth order.
+#[derive(Resource, Debug, Default, Serialize)]
+pub struct Lineage {
+    pub bees: Vec<LineageEntry>,
+}
+impl Lineage {
+    pub fn record_birth(
+        &mut self,
+        name: String,
+        faction: Faction,
+        parents: Vec<usize>,
+        genome: &Genome,
+    ) -> LineageId {
+        let id = self.bees.len();
+        let generation = parents
+            .iter()
+            .filter_map(|parent| self.bees.get(*parent))
+            .map(|parent| parent.generation + 1)
+            .max()
+            .unwrap_or(0);
+        let genes = BEE_GENES
+            .iter()
+            .map(|gene| (gene.name, genome.level(gene).unwrap_or(0.5)))
+            .collect();
+        for parent in &parents {
+            if let Some(parent) = self.bees.get_mut(*parent) {
+                if !parent.children.contains(&id) {
+                    parent.children.push(id);
+                }
+            }
+        }
+        self.bees.push(LineageEntry {
+            id,
+            name,
+            faction,
+            parents,
+            children: vec![],
+            generation,
+            genes,
+            alive: true,
+            age: 0.0,
+            kills: 0,
+            eggs: 0,
+        });
+        LineageId(id)
+    }
+
+    pub fn children(&self, id: usize) -> impl Iterator<Item = &LineageEntry> {
+        self.bees
+            .get(id)
+            .into_iter()
+            .flat_map(|bee| bee.children.iter().filter_map(|child| self.bees.get(*child)))
+    }
+
+    /// Everyone descended from the bee, in birth order.
+    #[must_use]
+    pub fn descendants(&self, id: usize) -> Vec<usize> {
+        let mut seen = vec![false; self.bees.len()];
+        let mut found = vec![];
+        let mut next: Vec<usize> = self.children(id).map(|bee| bee.id).collect();
+        while let Some(bee) = next.pop() {
+            // A bee can be reached through both parents
+            if std::mem::replace(&mut seen[bee], true) {
+                continue;
+            }
+            found.push(bee);
+            next.extend(self.children(bee).map(|child| child.id));
+        }
+        found.sort_unstable();
+        found
+    }
+
+    /// Parents, grandparents and so on, with how many generations back.
+    #[must_use]
+    pub fn ancestors(&self, id: usize, depth: usize) -> Vec<(usize, &LineageEntry)> {
+        let mut found = vec![];
+        let mut current = vec![id];
+        for back in 1..=depth {
+            current = current
+                .iter()
+                .filter_map(|bee| self.bees.get(*bee))
+                .flat_map(|bee| bee.parents.iter().copied())
+                .collect();
+            current.sort_unstable();
+            current.dedup();
+            found.extend(
+                current.iter().filter_map(|bee| self.bees.get(*bee)).map(|bee| (back, bee))
+            );
+        }
+        found
+    }
+
+    /// The faction's bee with the most children.
+    #[must_use]
+    pub fn most_prolific(&self, faction: Faction) -> Option<&LineageEntry> {
+        self.bees
+            .iter()
+            .filter(|bee| bee.faction == faction)
+            .map(|bee| (bee, bee.children.len()))
+            .filter(|(_, children)| *children > 0)
+            .max_by_key(|(bee, children)| (*children, std::cmp::Reverse(bee.id)))
+            .map(|(bee, _)| bee)
+    }
+
+    /// Average level of each gene for the faction's first generation and
+    /// for the bees alive now.
+    #[must_use]
+    pub fn gene_spread(&self, faction: Faction) -> Vec<(&'static str, f32, f32)> {
+        let average = |bees: &[&LineageEntry], gene: usize| {
+            bees.iter().map(|bee| bee.genes[gene].1).sum::<f32>() / bees.len().max(1) as f32
+        };
+        let bees: Vec<&LineageEntry> = self.bees
+            .iter()
+            .filter(|bee| bee.faction == faction)
+            .collect();
+        let Some(first) = bees.iter().map(|bee| bee.generation).min() else {
+            return vec![];
+        };
+        let founders: Vec<_> = bees.iter().copied().filter(|bee| bee.generation == first).collect();
+        let living: Vec<_> = bees.iter().copied().filter(|bee| bee.alive).collect();
+        BEE_GENES
+            .iter()
+            .enumerate()
+            .map(|(i, gene)| (gene.name, average(&founders, i), average(&living, i)))
+            .collect()
+    }
+
+    /// A Graphviz DOT family tree, coloured by faction.
+    #[must_use]
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph lineage {\n    node [style=filled];\n");
+        for bee in &self.bees {
+            let [r, g, b, _] = bee.faction.color().as_rgba_u8();
+            let _ = writeln!(
+                dot,
+                "    b{} [label=\"{}\\ngen {}\" fillcolor=\"#{:02x}{:02x}{:02x}\"{}];",
+                bee.id,
+                bee.name.replace('"', "'"),
+                bee.generation,
+                r, g, b,
+                if bee.alive { "" } else { " fontcolor=gray30" },
+            );
+            for parent in &bee.parents {
+                let _ = writeln!(dot, "    b{} -> b{};", parent, bee.id);
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+#[derive(Component)]
+struct LineagePanel;
+
+pub struct LineagePlugin;
+impl Plugin for LineagePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Lineage>()
+            .add_systems(OnEnter(GameState::InGame), lineage_setup)
+            .add_systems(FixedUpdate, update_lineage.in_set(SimSet::Simulate))
+            .add_systems(
+                Update,
+                (lineage_keys, update_lineage_panel).run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+fn lineage_setup(
+    mut commands: Commands,
+    assets: Res<AssetCol>,
+) {
+    commands.insert_resource(Lineage::default());
+
+    let mut panel = TextBundle::from_section(
+        "",
+        TextStyle {
+            font: assets.font.clone(),
+            font_size: 16.0,
+            color: Color::WHITE,
+        },
+    )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            right: Val::Px(15.0),
+            bottom: Val::Px(15.0),
+            padding: UiRect::all(Val::Px(8.0)),
+            display: Display::None,
+            ..default()
+        });
+    panel.background_color = BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.7));
+    commands.spawn((panel, LineagePanel, OnGameScreen));
+}
+
+/// Keep the records of living bees up to date.
+fn update_lineage(
+    beez: Query<(&LineageId, &BeeRecord, Option<&BeeKilled>)>,
+    mut lineage: ResMut<Lineage>,
+) {
+    for (id, record, killed) in &beez {
+        if let Some(entry) = lineage.bees.get_mut(id.0) {
+            entry.alive = killed.is_none();
+            entry.age = record.age;
+            entry.kills = record.kills;
+            entry.eggs = record.eggs;
+        }
+    }
+}
+
+fn lineage_keys(
+    keys: Res<Input<KeyCode>>,
+    mut panel: Query<&mut Style, With<LineagePanel>>,
+    lineage: Res<Lineage>,
+) {
+    if keys.just_pressed(KeyCode::L) {
+        for mut style in &mut panel {
+            style.display = match style.display {
+                Display::None => Display::Flex,
+                _ => Display::None,
+            };
+        }
+    }
+    if keys.just_pressed(KeyCode::X) {
+        export(&lineage);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn export(lineage: &Lineage) {
+    let json = match serde_json::to_string_pretty(lineage) {
+        Ok(json) => json,
+        Err(err) => {
+            error!("Couldn't serialize the lineage: {err}");
+            return;
+        }
+    };
+    let written = std::fs::write(format!("{EXPORT_NAME}.json"), json)
+        .and_then(|()| std::fs::write(format!("{EXPORT_NAME}.dot"), lineage.to_dot()));
+    match written {
+        Ok(()) => screen_print!(sec: 3.0, "Saved {EXPORT_NAME}.json and {EXPORT_NAME}.dot"),
+        Err(err) => error!("Couldn't save the lineage: {err}"),
+    }
+}
+
+/// No files on the web, so log it instead.
+#[cfg(target_arch = "wasm32")]
+fn export(lineage: &Lineage) {
+    if let Ok(json) = serde_json::to_string(lineage) {
+        info!("{json}");
+    }
+    info!("{}", lineage.to_dot());
+    screen_print!(sec: 3.0, "Lineage logged to the console");
+}
+
+fn update_lineage_panel(
+    mut panel: Query<(&mut Text, &Style), With<LineagePanel>>,
+    ids: Query<&LineageId>,
+    inspected: Res<Inspected>,
+    lineage: Res<Lineage>,
+    settings: Res<GameSettings>,
+) {
+    let Ok((mut text, style)) = panel.get_single_mut() else { return };
+    if style.display == Display::None {
+        return;
+    }
+    let mut info = String::new();
+
+    let inspected = inspected
+        .bee()
+        .and_then(|bee| ids.get(bee).ok())
+        .and_then(|LineageId(id)| lineage.bees.get(*id));
+    if let Some(bee) = inspected {
+        let _ = writeln!(info, "{} (GEN {})", bee.name, bee.generation);
+        for (back, ancestor) in lineage.ancestors(bee.id, ANCESTOR_DEPTH) {
+            let _ = writeln!(info, "{}{}", "  ".repeat(back), ancestor.name);
+        }
+        let children: Vec<&str> = lineage.children(bee.id).map(|bee| bee.name.as_str()).collect();
+        let _ = writeln!(
+            info,
+            "CHILDREN {}  DESCENDANTS {}",
+            children.len(),
+            lineage.descendants(bee.id).len(),
+        );
+        for child in children.iter().take(5) {
+            let _ = writeln!(info, "  {child}");
+        }
+        info.push('\n');
+    } else {
+        info += "Hover a bee for its family\n\n";
+    }
+
+    for faction in &settings.level.factions {
+        let _ = write!(info, "{:?}", faction);
+        match lineage.most_prolific(*faction) {
+            Some(bee) => {
+                let _ = writeln!(
+                    info,
+                    ": {} {} children, {} descendants",
+                    bee.name,
+                    bee.children.len(),
+                    lineage.descendants(bee.id).len(),
+                );
+            }
+            None => info.push('\n'),
+        }
+        // Genes that have moved the most since the first bees
+        let mut spread = lineage.gene_spread(*faction);
+        spread.sort_by(|a, b| (b.2 - b.1).abs().total_cmp(&(a.2 - a.1).abs()));
+        for (gene, founders, living) in spread.iter().take(2) {
+            let _ = writeln!(info, "  {gene} {founders:.2} -> {living:.2}");
+        }
+    }
+    text.sections[0].value = info;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::organism::genome::GeneRng;
+
+    /// A and B have C, who has D, who has G with A. E has F on the other side.
+    fn family() -> Lineage {
+        let mut rng = GeneRng::new(Some(47));
+        let mut lineage = Lineage::default();
+        let bees: [(&str, Faction, &[usize]); 7] = [
+            ("A", Faction::Red, &[]),
+            ("B", Faction::Red, &[]),
+            ("C", Faction::Red, &[0, 1]),
+            ("D", Faction::Red, &[2]),
+            ("E", Faction::Blue, &[]),
+            ("F", Faction::Blue, &[4]),
+            ("G", Faction::Red, &[3, 0]),
+        ];
+        for (name, faction, parents) in bees {
+            let genome = Genome::random(BEE_GENES, &mut *rng);
+            lineage.record_birth(name.to_string(), faction, parents.to_vec(), &genome);
+        }
+        lineage
+    }
+
+    #[test]
+    fn test_family_tree() {
+        let lineage = family();
+        let generations: Vec<u32> = lineage.bees.iter().map(|bee| bee.generation).collect();
+        assert_eq!(generations, [0, 0, 1, 2, 0, 1, 3]);
+
+        assert_eq!(lineage.bees[0].children, [2, 6]);
+        assert_eq!(lineage.bees[2].children, [3]);
+        assert_eq!(lineage.descendants(0), [2, 3, 6]);
+        assert_eq!(lineage.descendants(4), [5]);
+        assert!(lineage.descendants(6).is_empty());
+        assert!(lineage.descendants(99).is_empty());
+
+        let ancestors: Vec<(usize, usize)> = lineage
+            .ancestors(6, 2)
+            .into_iter()
+            .map(|(back, bee)| (back, bee.id))
+            .collect();
+        assert_eq!(ancestors, [(1, 0), (1, 3), (2, 2)]);
+
+        assert_eq!(lineage.most_prolific(Faction::Red).map(|bee| bee.id), Some(0));
+        assert_eq!(lineage.most_prolific(Faction::Blue).map(|bee| bee.id), Some(4));
+        assert!(lineage.most_prolific(Faction::Pink).is_none());
+    }
+
+    #[test]
+    fn test_gene_spread() {
+        let mut lineage = family();
+        for (id, level) in [(0, 0.2), (1, 0.4), (2, 0.5), (3, 0.6), (6, 0.8)] {
+            lineage.bees[id].genes[0].1 = level;
+        }
+        for id in [0, 1, 2] {
+            lineage.bees[id].alive = false;
+        }
+        let spread = lineage.gene_spread(Faction::Red);
+        let (gene, founders, living) = spread[0];
+        assert_eq!(gene, BEE_GENES[0].name);
+        assert!((founders - 0.3).abs() < 1e-5);
+        assert!((living - 0.7).abs() < 1e-5);
+        assert!(lineage.gene_spread(Faction::Pink).is_empty());
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let mut lineage = family();
+        lineage.bees[1].alive = false;
+        let dot = lineage.to_dot();
+        assert!(dot.starts_with("digraph lineage {"));
+        assert!(dot.trim_end().ends_with('}'));
+        for edge in ["b0 -> b2;", "b1 -> b2;", "b3 -> b6;", "b0 -> b6;", "b4 -> b5;"] {
+            assert!(dot.contains(edge), "missing {edge}");
+        }
+        assert_eq!(dot.matches(" -> ").count(), 6);
+        assert_eq!(dot.matches("fontcolor=gray30").count(), 1);
+    }
+}
@@ -7,7 +7,10 @@
 extern crate console_error_panic_hook;
 
 mod debug;
+pub mod difficulty;
+pub mod diplomacy;
 pub mod game;
+pub mod inspector;
 pub mod logo;
 pub mod organism;
 pub mod pathfinding;
@@ -15,11 +18,17 @@ pub mod splash;
 pub mod terrain;
 pub mod pointer;
 pub mod inventory;
+pub mod level;
+pub mod lineage;
 pub mod rumblebees;
 pub mod settings;
+pub mod simulation;
+pub mod time_control;
 
 use std::sync::OnceLock;
+use std::time::Duration;
 
+use bevy::asset::ChangeWatcher;
 use bevy::window::{Cursor, CursorIcon, PrimaryWindow};
 use bevy::{asset::HandleId, prelude::*};
 use bevy_debug_text_overlay::{screen_print, OverlayPlugin};
@@ -75,9 +84,11 @@ fn main() {
                     ..default()
                 }),
                 ..default()
-            }),
+            })
+            .set(asset_plugin()),
             //.set(ImagePlugin::default_nearest()),
             AudioPlugin,
+            settings::SettingsPlugin,
         ))
         .add_loading_state(LoadingState::new(GameState::Loading).continue_to_state(GameState::Splash))
         .add_collection_to_loading_state::<_, AssetCol>(GameState::Loading)
@@ -94,7 +105,12 @@ fn main() {
             game::GamePlugin,
             terrain::TerrainPlugin,
             inventory::UIPlugin,
-            rumblebees::RumblebeePlugin
+            rumblebees::RumblebeePlugin,
+            simulation::SimulationPlugin,
+            time_control::TimeControlPlugin,
+            diplomacy::DiplomacyPlugin,
+            inspector::InspectorPlugin,
+            lineage::LineagePlugin,
         ))
         .run();
 }
@@ -114,6 +130,37 @@ pub struct AssetCol {
     #[asset(path = "img/arms.png")]
     pub arms: Handle<TextureAtlas>,
 
+    // Bee parts. Torsos are in the same order as the bodies in chars.png
+    #[asset(paths(
+        "img/Creatures/Torsos/blue-torso.png",
+        "img/Creatures/Torsos/pink-torso.png",
+        "img/Creatures/Torsos/purple-torso.png",
+        "img/Creatures/Torsos/green-torso.png",
+        "img/Creatures/Torsos/red-torso.png",
+        "img/Creatures/Torsos/yellow-torso.png",
+    ), collection(typed))]
+    pub torsos: Vec<Handle<Image>>,
+    #[asset(paths(
+        "img/Creatures/Faces/happy-expression.png",
+        "img/Creatures/Faces/angry-expression.png",
+        "img/Creatures/Faces/strained-expression.png",
+    ), collection(typed))]
+    pub faces: Vec<Handle<Image>>,
+    #[asset(paths(
+        "img/Creatures/Faces/BeardMid-small.png",
+        "img/Creatures/Faces/BeardLong-small.png",
+    ), collection(typed))]
+    pub beards: Vec<Handle<Image>>,
+    #[asset(paths(
+        "img/Creatures/Wings/wings-up.png",
+        "img/Creatures/Wings/wings-mid.png",
+        "img/Creatures/Wings/wings-down.png",
+    ), collection(typed))]
+    pub wings: Vec<Handle<Image>>,
+    // Fights and growing up
+    #[asset(path = "img/dust.png")]
+    pub dust: Handle<Image>,
+
     #[asset(texture_atlas(tile_size_x = 40.0, tile_size_y = 40.0, columns = 16, rows = 5))]
     #[asset(path = "img/tiles.png")]
     pub tiles: Handle<TextureAtlas>,
@@ -124,6 +171,18 @@ pub struct AssetCol {
     tune: Handle<AudioSource>,
     #[asset(path = "font/Curse of the Zombie.ttf")]
     font: Handle<Font>,
+
+    #[asset(path = "settings.ron")]
+    settings: Handle<settings::GameSettings>,
+}
+
+/// Watch the assets folder so settings can be tweaked while playing.
+fn asset_plugin() -> AssetPlugin {
+    AssetPlugin {
+        #[cfg(not(target_arch = "wasm32"))]
+        watch_for_changes: ChangeWatcher::with_delay(Duration::from_millis(200)),
+        ..default()
+    }
 }
 
 fn setup(
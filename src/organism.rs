@@ -1,8 +1,10 @@
 use rand::Rng;
 
+use crate::game::Upright;
 use crate::{prelude::*, AssetCol};
 
 pub mod gene;
+pub mod genome;
 
 pub fn create_random_organsim(
     commands: &mut Commands,
@@ -33,56 +35,79 @@ impl Organism {
         }
     }
     pub fn random_parts(b: &mut ChildBuilder, font: Handle<Font>, (x, y): (f32, f32)) {
-        b.spawn(Text2dBundle {
-            text: Text::from_section(
-                random_name(),
-                TextStyle {
-                    font,
-                    font_size: 60.0,
-                    color: Color::GREEN,
-                },
-            ),
-            transform: Transform::from_xyz(x, y, 1.0),
-            ..Default::default()
-        });
-        //NameTag::new(font, random_name(), position));
+        let name = random_name(&mut rand::thread_rng());
+        b.spawn(NameTag::new(font, name, 60.0, Color::GREEN, (x, y)));
         b.spawn(Torso);
     }
 }
 
+/// A name floating by an organism. It stays readable when they turn around.
+#[derive(Debug, Component)]
 pub struct NameTag;
-impl NameTag {}
+impl NameTag {
+    #[must_use]
+    pub fn new(
+        font: Handle<Font>,
+        name: String,
+        font_size: f32,
+        color: Color,
+        (x, y): (f32, f32),
+    ) -> (Self, Upright, Text2dBundle) {
+        let bundle = Text2dBundle {
+            text: Text::from_section(name, TextStyle { font, font_size, color }),
+            transform: Transform::from_xyz(x, y, 1.0),
+            ..Default::default()
+        };
+        (Self, Upright, bundle)
+    }
+}
 
 /// A body part.
 #[derive(Debug, Component)]
 pub struct Torso;
 impl Torso {}
 
+/// A silly name. Pass a seeded rng to get the same names every game.
 #[must_use]
-pub fn random_name() -> String {
-    const NO_PREFIX_CHANCE: usize = 4;
-    const PREFIX: &[&str] = &["Mc", "El"];
-    const NO_SUFFIX_CHANCE: usize = 4;
-    const SUFFIX: &[&str] = &["son", "y", "athy"];
-    const FIRST: &[&str] = &["John", "Daniel", "Tom"];
-    const LAST: &[&str] = &["Daniels", "Patrik", "Col"];
-    let mut rng = rand::thread_rng();
-    let mut name = String::with_capacity(32);
-    if let Some(prefix) = PREFIX.get(rng.gen_range(0..PREFIX.len() + NO_PREFIX_CHANCE)) {
-        name += prefix;
-    }
-    name += FIRST[rng.gen_range(0..FIRST.len())];
-    if let Some(suffix) = SUFFIX.get(rng.gen_range(0..SUFFIX.len() + NO_SUFFIX_CHANCE)) {
-        name += suffix;
+pub fn random_name(rng: &mut impl Rng) -> String {
+    const NO_PREFIX_CHANCE: usize = 6;
+    const PREFIX: &[&str] = &["Mc", "El", "Von ", "O'", "De"];
+    const NO_SUFFIX_CHANCE: usize = 6;
+    const SUFFIX: &[&str] = &["son", "y", "athy", "kins", "bottom", "worth"];
+    const FIRST: &[&str] = &[
+        "John", "Daniel", "Tom", "Buzz", "Bumble", "Honey", "Nectar", "Pollen",
+        "Waggle", "Stinger", "Clover", "Fuzz", "Dot", "Beatrix",
+    ];
+    const LAST: &[&str] = &[
+        "Daniels", "Patrik", "Col", "Hive", "Comb", "Drone", "Petal", "Thorax",
+        "Stripe", "Hum", "Wax", "Jelly",
+    ];
+    const MADE_UP_CHANCE: f64 = 0.25; // First name from the bits below instead
+    const START: &[&str] = &["B", "Z", "Fl", "Dr", "H", "W", "St", "N"];
+    const VOWEL: &[&str] = &["a", "e", "i", "o", "u", "ee", "oo"];
+    const END: &[&str] = &["zz", "b", "mble", "ney", "x", "p", "nk"];
+
+    fn pick(rng: &mut impl Rng, list: &[&'static str]) -> &'static str {
+        list[rng.gen_range(0..list.len())]
     }
-    name.push(' ');
-    if let Some(prefix) = PREFIX.get(rng.gen_range(0..PREFIX.len() + NO_PREFIX_CHANCE)) {
-        name += prefix;
+    fn maybe(rng: &mut impl Rng, list: &[&'static str], none_chance: usize) -> &'static str {
+        list.get(rng.gen_range(0..list.len() + none_chance)).copied().unwrap_or("")
     }
-    name += LAST[rng.gen_range(0..LAST.len())];
-    if let Some(suffix) = SUFFIX.get(rng.gen_range(0..SUFFIX.len() + NO_SUFFIX_CHANCE)) {
-        name += suffix;
+
+    let mut name = String::with_capacity(32);
+    name += maybe(rng, PREFIX, NO_PREFIX_CHANCE);
+    if rng.gen_bool(MADE_UP_CHANCE) {
+        name += pick(rng, START);
+        name += pick(rng, VOWEL);
+        name += pick(rng, END);
+    } else {
+        name += pick(rng, FIRST);
     }
+    name += maybe(rng, SUFFIX, NO_SUFFIX_CHANCE);
+    name.push(' ');
+    name += maybe(rng, PREFIX, NO_PREFIX_CHANCE);
+    name += pick(rng, LAST);
+    name += maybe(rng, SUFFIX, NO_SUFFIX_CHANCE);
 
     name
 }
@@ -1,21 +1,98 @@
 //! Genetic information.
+use macros::Stat;
+
 use crate::prelude::*;
 
 /// A gene name.
 pub struct Gene<'a> {
     pub name: &'a str,
     pub sequence: &'a str,
+    pub mutation: f32, // Scales the mutation rate when breeding
 }
 
-/// A trait carried by an organism.
+/// A trait carried by an organism. Derive it with `#[derive(Stat)]`, which
+/// reads a `#[stat(..)]` attribute for the gene and the value range.
+///
+/// A stat with a `min`/`max` is used as its `value`, so the range in the
+/// attribute is what the gene does in game. A stat left at the default
+/// 0..1 is tuned by a setting instead, through `scale`.
 pub trait Stat {
-    fn gene(&self) -> &'static Gene<'static>;
+    const GENE: &'static Gene<'static>;
+    const MIN: f32;
+    const MAX: f32;
+    const DEFAULT: f32; // Without the gene
+
+    fn from_value(value: f32) -> Self where Self: Sized;
+    fn value(&self) -> f32;
+
+    fn gene(&self) -> &'static Gene<'static> {
+        Self::GENE
+    }
+    /// `MIN` at level 0, `MAX` at level 1.
+    fn from_level(level: f32) -> Self where Self: Sized {
+        Self::from_value(Self::MIN + (Self::MAX - Self::MIN) * level.clamp(0.0, 1.0))
+    }
+    /// 0..1 where the value sits in the range.
+    fn level(&self) -> f32 {
+        (self.value() - Self::MIN) / (Self::MAX - Self::MIN)
+    }
+    /// A multiplier around 1.0: `1 - spread` at level 0, `1 + spread` at level 1.
+    fn scale(&self, spread: f32) -> f32 {
+        1.0 + spread * (self.level() * 2.0 - 1.0)
+    }
 }
 
+/// Max health multiplier.
+#[derive(Stat)]
+#[stat(gene = "chonkiness", min = 0.7, max = 1.3, default = 1.0)]
 pub struct Chonk(f32);
-impl Chonk {
-    pub const GENE: Gene<'static> = gene!("chonkiness");
-}
+
+/// Flying speed. How much it varies is the `rumblebee_speed_variance` setting.
+#[derive(Stat)]
+#[stat(gene = "zoominess")]
+pub struct Zoom(f32);
+
+/// Aging rate multiplier, lower lives longer.
+#[derive(Stat)]
+#[stat(gene = "stamina", min = 1.3, max = 0.7, default = 1.0)]
+pub struct Stamina(f32);
+
+/// Punch damage multiplier.
+#[derive(Stat)]
+#[stat(gene = "brawn", min = 0.7, max = 1.3, default = 1.0)]
+pub struct Brawn(f32);
+
+/// Digging power multiplier.
+#[derive(Stat)]
+#[stat(gene = "diggy", min = 0.7, max = 1.3, default = 1.0, mutation = 0.5)]
+pub struct Diggy(f32);
+
+/// Beard style, mid or long.
+#[derive(Stat)]
+#[stat(gene = "fluffiness")]
+pub struct Fluff(f32);
+
+/// Wing size.
+#[derive(Stat)]
+#[stat(gene = "wingspan", min = 0.8, max = 1.25, default = 1.0)]
+pub struct Wingspan(f32);
+
+/// Body colour brightness.
+#[derive(Stat)]
+#[stat(gene = "shade", min = 0.7, max = 1.0, default = 1.0)]
+pub struct Shade(f32);
+
+/// Every gene a bee carries.
+pub const BEE_GENES: &[&Gene<'static>] = &[
+    Chonk::GENE,
+    Zoom::GENE,
+    Stamina::GENE,
+    Brawn::GENE,
+    Diggy::GENE,
+    Fluff::GENE,
+    Wingspan::GENE,
+    Shade::GENE,
+];
 
 #[cfg(test)]
 mod test {
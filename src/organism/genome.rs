@@ -0,0 +1,149 @@
+//! An organism's DNA: a base sequence for each of its genes.
+//!
+//! Children mix their parents' DNA with crossover and pick up the odd
+//! mutation. All the randomness comes from the [`GeneRng`] resource, so a
+//! seeded game breeds the same bees every time.
+use bevy::utils::HashMap;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::organism::gene::{Gene, Stat};
+use crate::prelude::*;
+
+const BASES: [u8; 4] = [b'a', b'c', b'g', b't'];
+
+/// Random numbers for breeding. Seeded from `gene_seed` when set.
+#[derive(Resource, Deref, DerefMut)]
+pub struct GeneRng(StdRng);
+impl GeneRng {
+    #[must_use]
+    pub fn new(seed: Option<u64>) -> Self {
+        Self(seed.map_or_else(StdRng::from_entropy, StdRng::seed_from_u64))
+    }
+}
+impl Default for GeneRng {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+fn random_base(rng: &mut impl Rng) -> u8 {
+    BASES[rng.gen_range(0..BASES.len())]
+}
+
+#[derive(Component, Debug, Clone, Default)]
+pub struct Genome {
+    dna: HashMap<&'static str, Vec<u8>>,
+}
+impl Genome {
+    /// Random bases for each gene.
+    #[must_use]
+    pub fn random(genes: &[&'static Gene<'static>], rng: &mut impl Rng) -> Self {
+        let dna = genes
+            .iter()
+            .map(|gene| {
+                let bases = (0..gene.sequence.len()).map(|_| random_base(rng)).collect();
+                (gene.name, bases)
+            })
+            .collect();
+        Self { dna }
+    }
+
+    /// A child of one or two parents. Each gene is cut at a random point,
+    /// taking the start from one parent and the rest from the other, then
+    /// every base has a chance of changing: `mutation_rate` scaled by the
+    /// gene's own `mutation`.
+    #[must_use]
+    pub fn breed(
+        genes: &[&'static Gene<'static>],
+        a: &Genome,
+        b: Option<&Genome>,
+        mutation_rate: f32,
+        rng: &mut impl Rng,
+    ) -> Self {
+        let dna = genes
+            .iter()
+            .map(|gene| {
+                let mut bases = match (a.dna.get(gene.name), b.and_then(|b| b.dna.get(gene.name))) {
+                    (Some(x), Some(y)) => {
+                        let (first, second) = if rng.gen_bool(0.5) { (x, y) } else { (y, x) };
+                        let cut = rng.gen_range(0..=first.len().min(second.len()));
+                        first[..cut].iter().chain(&second[cut..]).copied().collect()
+                    }
+                    (Some(x), None) | (None, Some(x)) => x.clone(),
+                    (None, None) => (0..gene.sequence.len()).map(|_| random_base(rng)).collect(),
+                };
+                let chance = f64::from((mutation_rate * gene.mutation).clamp(0.0, 1.0));
+                for base in &mut bases {
+                    if rng.gen_bool(chance) {
+                        *base = random_base(rng);
+                    }
+                }
+                (gene.name, bases)
+            })
+            .collect();
+        Self { dna }
+    }
+
+    #[must_use]
+    pub fn bases(&self, gene: &Gene) -> Option<&[u8]> {
+        self.dna.get(gene.name).map(Vec::as_slice)
+    }
+
+    /// 0..1 how closely the DNA matches the gene, or `None` without it. No
+    /// matching bases is 0 and an exact match 1. Random DNA matches a
+    /// quarter of its bases, which is put at 0.5.
+    #[must_use]
+    pub fn level(&self, gene: &Gene) -> Option<f32> {
+        let bases = self.dna.get(gene.name).filter(|bases| !bases.is_empty())?;
+        let matches = bases.iter().zip(gene.sequence.bytes()).filter(|(a, b)| **a == *b).count();
+        let matched = matches as f32 / bases.len() as f32;
+        let chance = 1.0 / BASES.len() as f32;
+        Some(if matched < chance {
+            0.5 * matched / chance
+        } else {
+            0.5 + 0.5 * (matched - chance) / (1.0 - chance)
+        })
+    }
+
+    /// The stat this genome gives, or its default without the gene.
+    #[must_use]
+    pub fn express<S: Stat>(&self) -> S {
+        self.level(S::GENE).map_or_else(|| S::from_value(S::DEFAULT), S::from_level)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::organism::gene::{BEE_GENES, Chonk};
+
+    #[test]
+    fn test_breed_is_seeded() {
+        let breed = || {
+            let mut rng = GeneRng::new(Some(7));
+            let a = Genome::random(BEE_GENES, &mut *rng);
+            let b = Genome::random(BEE_GENES, &mut *rng);
+            Genome::breed(BEE_GENES, &a, Some(&b), 0.1, &mut *rng)
+        };
+        let (x, y) = (breed(), breed());
+        for gene in BEE_GENES {
+            assert_eq!(x.dna.get(gene.name), y.dna.get(gene.name));
+        }
+    }
+
+    #[test]
+    fn test_express_range() {
+        let genome = |bases: fn(u8) -> u8| {
+            let dna = Chonk::GENE.sequence.bytes().map(bases).collect();
+            Genome { dna: std::iter::once((Chonk::GENE.name, dna)).collect() }
+        };
+        let exact = genome(|base| base);
+        let opposite = genome(|base| if base == b'a' { b't' } else { b'a' });
+        assert!((exact.level(Chonk::GENE).unwrap() - 1.0).abs() < 1e-5);
+        assert!(opposite.level(Chonk::GENE).unwrap().abs() < 1e-5);
+        assert!((exact.express::<Chonk>().value() - Chonk::MAX).abs() < 1e-5);
+        assert!((opposite.express::<Chonk>().value() - Chonk::MIN).abs() < 1e-5);
+        assert!((Genome::default().express::<Chonk>().value() - Chonk::DEFAULT).abs() < 1e-5);
+    }
+}
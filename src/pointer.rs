@@ -1,5 +1,6 @@
 use crate::prelude::*;
-use crate::terrain::Tile;
+use crate::settings::GameSettings;
+use crate::terrain::{Faction, Tile};
 use bevy::input::touch::{Touches,TouchPhase};
 
 #[derive(Resource, Debug)]
@@ -9,11 +10,15 @@ pub struct Pointer {
     pub pressed: bool,
     pub released: bool,
     pub tile: Tile,
+    /// Right button places fertilizer instead of dirt
+    pub fertilizing: bool,
+    pub fertilizer: Faction,
 }
 impl Pointer {
     pub fn set_active_item(&mut self, tile: Tile) {
         if self.pressed {
             self.tile = match tile {
+                Tile::Air if self.fertilizing => Tile::Poo { style: self.fertilizer.egg_style() },
                 Tile::Air => Tile::Dirt { style: 1, topsoil: true } ,
                 Tile::Rock { .. } => tile,
                 _ => Tile::Air,
@@ -39,6 +44,8 @@ impl Default for Pointer {
             pressed: false,
             released: false,
             tile: Tile::Unknown,
+            fertilizing: false,
+            fertilizer: Faction::Red,
         }
     }
 }
@@ -48,6 +55,8 @@ pub fn update_pointer(
     mut cursor_moved_events: EventReader<CursorMoved>,
     mut pointer: ResMut<Pointer>,
     mouse: Res<Input<MouseButton>>,
+    keys: Res<Input<KeyCode>>,
+    settings: Res<GameSettings>,
     touches: Res<Touches>,
     mut touch_evr: EventReader<TouchInput>,
 ) {
@@ -79,12 +88,32 @@ pub fn update_pointer(
         pointer.pos.y = finger.position().y;
     }
 
+    if keys.just_pressed(KeyCode::F) {
+        // Cycle through the level's factions
+        let factions = &settings.level.factions;
+        if let Some(first) = factions.first() {
+            pointer.fertilizer = factions
+                .iter()
+                .skip_while(|faction| **faction != pointer.fertilizer)
+                .nth(1)
+                .copied()
+                .unwrap_or(*first);
+        }
+        screen_print!(sec: 2.0, "Fertilizer: {:?}", pointer.fertilizer);
+    }
+
     pointer.pressed
         = mouse.just_pressed(MouseButton::Left)
+        || mouse.just_pressed(MouseButton::Right)
         || touches.any_just_pressed();
     pointer.released = mouse.just_released(MouseButton::Left)
         || touches.any_just_released();
-    pointer.is_down = mouse.pressed(MouseButton::Left) || touch_move;
+    pointer.is_down = mouse.pressed(MouseButton::Left)
+        || mouse.pressed(MouseButton::Right)
+        || touch_move;
+    if pointer.pressed {
+        pointer.fertilizing = mouse.just_pressed(MouseButton::Right);
+    }
 
     for cursor_moved in &mut cursor_moved_events {
         for (cam_t, cam) in camera_q.iter() {
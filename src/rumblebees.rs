@@ -4,62 +4,89 @@ use crate::game::{
     Health
 };
 use crate::AssetCol;
-use crate::settings::{RUMBLEBEE_SPEED_MAX, RUMBLEBEE_PER_EGG_SPEEDUP_PERC, RUMBLEBEE_SPEED_START, RUMBLEBEE_SPEED_VARIANCE, AGING_AMOUNT_PER_TICK};
-use bevy_ecs_tilemap::helpers::square_grid::neighbors::Neighbors;
-use rand::seq::IteratorRandom;
+use crate::diplomacy::Diplomacy;
+use crate::simulation::{SimPosition, SimSet, sim_delta};
+use crate::settings::GameSettings;
 use crate::terrain::{GAP_LEFT, Tile, Egg, Faction, tilepos_to_px, find_empty_tile, px_to_tilepos};
 use crate::{prelude::*, GameState};
 use bevy::math::swizzles::Vec3Swizzles;
 use bevy::prelude::*;
-use bevy::utils::Instant;
 use rand::Rng;
 use std::ops::Sub;
-use std::time::Duration;
 
 use crate::Layers;
+use crate::organism::gene::{BEE_GENES, Brawn, Chonk, Diggy, Stamina, Stat, Zoom};
+use crate::organism::genome::{GeneRng, Genome};
+use crate::organism::{NameTag, random_name};
+use crate::lineage::{Lineage, LineageId};
+
+pub mod behaviour;
+pub mod brawl;
+pub mod life;
+pub mod looks;
+pub mod needs;
+pub mod spatial;
+use behaviour::{Decision, DecisionTag, Options};
+use life::LifeStage;
+use needs::Hunger;
+use spatial::BeeGrid;
 
 /*
 Systems:
 1. setup.
 2. birth_a_bee. <BeeBorn>,
-3. find_target. <Rumblebee> Without<Pathfinding, BeeFighter>
+3. behaviours score idle bees, decide picks the best (behaviour.rs)
 4. egg_collisions. <Rumblebee>
-5. fight_collisions. <RumbeBee> Without<BeeFighter>
+5. fight_collisions. <RumbeBee> Without<Inactive>
 6. became_a_fighter. <RumbleBee> Added<BeeFighter>
 7. bee_fight <BeeFight>. beez With<BeeFighter>
+   pull_back_arms <Punching>
+(5-7 are in brawl.rs)
 8. bee_dead
-  .after(find_target)
   .after(bee_fight) Added<BeeKilled>
 
 Setup:
-1. spawns some <BeeBorn> entities.
+1. spawns some <BeeBorn> entities, reseeds <GeneRng>.
 
-Update:
+FixedUpdate (SimSet::Simulate):
 2. birth_a_bee. <BeeBorn> <Navmesh>
    - despawn <BeeBorn>
    - spawn <RumbleBee>
      - if has pos, set it else find blank tile.
      - set pos + z index.
-
-3. find_target. <Rumblebee> Without<Pathfinding, BeeFighter>
-   - add <Pathfinding>
-
-4. egg_collisions. <Rumblebee>
-   - spawn <BeeBorn> on hit egg
-
-5. fight_collisions. <RumbeBee> Without<BeeFighter>
-   - for each pair, if collision:
-     - add <BeeFighter> to each
-     - spawn <BeeFight bee1 bee2>
+     - <Genome> bred from parent + mate, <BeeStats> from that.
+     - body parts from the genome (looks.rs), <Face> follows health + fighting.
+     - starts as a <LifeStage::Larva>, grow_up moves it on (life.rs).
+
+3. BeeAi::Score, then BeeAi::Decide (behaviour.rs). <Rumblebee> Idle:
+   Without<Pathfinding, BeeFighter, Inactive, Stuck, Resting>
+   - each behaviour (behaviour/*.rs) scores 0..1 and offers a plan to <Options>
+     collect egg, dig, feed, fight, flee, rest, wander
+   - decide paths the best plan, or the next if there's no way: add
+     <Pathfinding> or <Resting>, record <Decision>
+   - forget_options clears what's left, for bees that stopped being idle
+
+4. egg_collisions. <Egg>, after build_bee_grid
+   - for each egg, the first bee in <BeeGrid> range that can collect it
+   - spawn <BeeBorn> on hit egg, the closest same-faction bee is the mate
+
+5. fight_collisions. <RumbeBee> Without<Inactive>, after bee_fight
+   - larvae don't fight
+   - hostile bees in range are linked, along with their current fights
+   - each linked group joins its first <BeeFight> (others are emptied)
+     or spawns a new one with a dust cloud
+   - add <BeeFighter { fight }> to each member
 
 6. became_a_fighter. <RumbleBee> Added<BeeFighter>
    - remove <Pathfinding>
 
 7. bee_fight. <BeeFight>, beez With<BeeFighter>
-   - after 5 secs:
-     despawn <BeeFight>
-     bee1: remove <BeeFighter>
-     bee2: add <BeeKilled>
+   - every punch_interval_secs each bee punches, split across its enemies'
+     <Health> (its arm gets <Punching>, pull_back_arms resets it)
+   - a bee at 0 health gets <BeeKilled>; a beaten bee may retreat
+     (add <Inactive>) and leaves the fight
+   - after fight_duration_secs, or with no enemies left, the fight ends:
+     despawn <BeeFight>, remove <BeeFighter> from everyone.
 
 8. bee_dead. Added<BeeKilled>
    - despawn recursive.
@@ -69,20 +96,30 @@ pub struct RumblebeePlugin;
 impl Plugin for RumblebeePlugin {
     fn build(&self, app: &mut App) {
         app
+            .init_resource::<BeeGrid>()
+            .init_resource::<GeneRng>()
+            .add_plugins(behaviour::BehaviourPlugin)
             .add_systems(OnEnter(GameState::InGame), rumblebee_setup)
             .add_systems(
-                Update,
+                FixedUpdate,
                 (
                     do_nothing_for_a_bit,
                     birth_a_bee,
                     get_older,
-                    find_target,
-                    egg_collisions,
-                    fight_collisions,
-                    bee_fight,
-                    became_a_fighter,
+                    life::grow_up.after(get_older),
+                    life::fade_poofs,
+                    needs::get_hungry,
+                    needs::feed,
+                    // Positions are final for the step once bees have moved
+                    spatial::build_bee_grid.after(crate::game::follow_path),
+                    egg_collisions.after(spatial::build_bee_grid),
+                    brawl::bee_fight,
+                    brawl::fight_collisions.after(brawl::bee_fight).after(spatial::build_bee_grid),
+                    brawl::pull_back_arms,
+                    brawl::became_a_fighter,
+                    looks::update_faces,
                     bee_dead,
-                ).run_if(in_state(GameState::InGame)),
+                ).in_set(SimSet::Simulate),
             );
     }
 }
@@ -92,14 +129,50 @@ pub struct RumbleBee {
     pub faction: terrain::Faction,
 }
 
+/// What a bee's genes made of it.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct BeeStats {
+    pub max_health: f32,
+    pub aging: f32, // Multiplies the aging rate
+    pub strength: f32, // Multiplies punch damage
+    pub dig_power: u8,
+    pub speed: f32, // In their prime
+}
+impl BeeStats {
+    #[must_use]
+    pub fn from_genome(genome: &Genome, base_speed: f32, settings: &GameSettings) -> Self {
+        let dig = f32::from(settings.dig_power) * genome.express::<Diggy>().value();
+        Self {
+            speed: base_speed * genome.express::<Zoom>().scale(settings.rumblebee_speed_variance),
+            max_health: 100.0 * genome.express::<Chonk>().value(),
+            aging: genome.express::<Stamina>().value(),
+            strength: genome.express::<Brawn>().value(),
+            dig_power: dig.round().clamp(1.0, 255.0) as u8,
+        }
+    }
+}
+
+/// A bee's life so far, for the inspector.
+#[derive(Component, Debug, Default, Clone)]
+pub struct BeeRecord {
+    pub parent: Option<String>, // The parent's name
+    pub age: f32, // Seconds
+    pub kills: u32,
+    pub eggs: u32, // Collected
+}
+
 #[derive(Component)]
 pub struct Inactive {
     pub timer: Timer
 }
 
+/// Has a beard.
 #[derive(Component)]
 pub struct OldTimer;
 
+/// Size of a bee sprite in its prime.
+pub const BEE_SCALE: f32 = 50.0 / 80.0;
+
 // Container for grouping in Debug plugin
 #[derive(Component)]
 struct BeeContainer;
@@ -108,6 +181,8 @@ struct BeeContainer;
 struct BeeBorn {
     pos: Option<Vec2>,
     faction: Faction,
+    parent: Option<Entity>, // The bee that collected the egg
+    mate: Option<Entity>, // A bee of the same faction nearby
 }
 
 // Punch arm
@@ -115,26 +190,16 @@ struct BeeBorn {
 pub struct ArmAnim;
 
 #[derive(Component)]
-struct BeeFight {
-    bee1: Entity,
-    bee2: Entity,
-    started: Instant
-}
-
-#[derive(Component)]
-struct BeeFighter;
-
-#[derive(Component)]
-struct BeeKilled;
+pub struct BeeKilled;
 
 fn do_nothing_for_a_bit(
     mut commands: Commands,
     mut beez: Query<(Entity, &mut Inactive)>,
-    time: Res<Time>
+    fixed_time: Res<FixedTime>
 ) {
 
     for (ent, mut inactive) in beez.iter_mut() {
-        inactive.timer.tick(time.delta());
+        inactive.timer.tick(fixed_time.period);
         if inactive.timer.finished() {
             commands.entity(ent).remove::<Inactive>();
         }
@@ -143,21 +208,22 @@ fn do_nothing_for_a_bit(
 
 fn rumblebee_setup(
     mut commands: Commands,
+    settings: Res<GameSettings>,
 ){
     commands.spawn((SpatialBundle { ..default() }, BeeContainer, OnGameScreen))
         .insert(Name::new("Beez"));
 
-    // Make the beez
-    commands.spawn(BeeBorn {
-        pos: Some(Vec2 { x: 620.0, y: 220.0 }),
-        faction: Faction::Red
-    });
-    commands.spawn(BeeBorn {
-        pos: Some(Vec2 { x: 420.0, y: 220.0 }),
-        faction: Faction::Blue
-    });
-
-
+    // Make the beez: one per faction. Past the first two, anywhere free.
+    let start_spots = [Vec2 { x: 620.0, y: 220.0 }, Vec2 { x: 420.0, y: 220.0 }];
+    for (i, faction) in settings.level.factions.iter().enumerate() {
+        commands.spawn(BeeBorn {
+            pos: start_spots.get(i).copied(),
+            faction: *faction,
+            parent: None,
+            mate: None,
+        });
+    }
+    commands.insert_resource(GeneRng::new(settings.gene_seed));
 }
 
 fn birth_a_bee(
@@ -165,12 +231,18 @@ fn birth_a_bee(
     assets: Res<AssetCol>,
     bees: Query<(Entity, &BeeBorn)>,
     parent: Query<Entity, With<BeeContainer>>,
+    genomes: Query<&Genome>,
+    names: Query<&Name>,
+    lineage_ids: Query<&LineageId>,
+    mut lineage: ResMut<Lineage>,
+    mut gene_rng: ResMut<GeneRng>,
     tilemap: Query<(
         &TilemapSize,
         &TilemapGridSize,
         &NavmeshPair,
     ), Without<RumbleBee>>,
-    mut game_data: ResMut<GameData>
+    mut game_data: ResMut<GameData>,
+    settings: Res<GameSettings>
 ) {
     let (map_size, grid_size, navmesh) = tilemap.single();
     let mut rng = rand::thread_rng();
@@ -182,9 +254,9 @@ fn birth_a_bee(
         // Speed up a bit every bee
         game_data.bee_base_speed =
             game_data.bee_base_speed +
-            ((RUMBLEBEE_SPEED_MAX - RUMBLEBEE_SPEED_START)
-             * RUMBLEBEE_PER_EGG_SPEEDUP_PERC)
-            .min(RUMBLEBEE_SPEED_MAX); // Cap it to max
+            ((settings.rumblebee_speed_max - settings.rumblebee_speed_start)
+             * settings.rumblebee_per_egg_speedup_perc)
+            .min(settings.rumblebee_speed_max); // Cap it to max
 
         screen_print!(sec: 5.0, "bee base speed: {:?}", game_data.bee_base_speed);
 
@@ -201,32 +273,62 @@ fn birth_a_bee(
             Layers::MIDGROUND + rng.gen_range(0..100) as f32
         });
 
-        let is_blue = if spawn.faction == Faction::Blue { true } else { false };
-        let bee_sprite = SpriteSheetBundle {
-            texture_atlas: assets.chars.clone(),
-            transform: Transform::from_translation(pos).with_scale(Vec3::splat(50.0/80.0)),
-            sprite: TextureAtlasSprite::new(if is_blue {0} else {1}),
+        let bee_sprite = SpatialBundle {
+            transform: Transform::from_translation(pos)
+                .with_scale(Vec3::splat(BEE_SCALE * LifeStage::Larva.size())),
             ..default()
         };
 
+        // Take after the parents. The first bees are random.
+        let mate = spawn.mate.and_then(|mate| genomes.get(mate).ok());
+        let genome = match spawn.parent.and_then(|parent| genomes.get(parent).ok()) {
+            Some(parent) => {
+                Genome::breed(BEE_GENES, parent, mate, settings.mutation_rate, &mut **gene_rng)
+            }
+            None => Genome::random(BEE_GENES, &mut **gene_rng),
+        };
+        let stats = BeeStats::from_genome(&genome, game_data.bee_base_speed, &settings);
+
+        let name = random_name(&mut **gene_rng);
+        let parents = [spawn.parent, spawn.mate]
+            .into_iter()
+            .flatten()
+            .filter_map(|parent| lineage_ids.get(parent).ok())
+            .map(|id| id.0)
+            .collect();
+        let lineage_id = lineage.record_birth(name.clone(), spawn.faction, parents, &genome);
+        let record = BeeRecord {
+            parent: spawn.parent
+                .and_then(|parent| names.get(parent).ok())
+                .map(ToString::to_string),
+            ..default()
+        };
+        let [torso, wings, face] =
+            looks::spawn_parts(&mut commands, &assets, spawn.faction, &genome);
+        let tag = commands.spawn(NameTag::new(
+            assets.font.clone(), name.clone(), 22.0, spawn.faction.color(), (0.0, 48.0)
+        )).id();
+        let decision = commands.spawn(DecisionTag::new(assets.font.clone())).id();
         let bee = commands.spawn((
             bee_sprite,
             RumbleBee {
-                faction: match is_blue {
-                    true => Faction::Blue,
-                    false => Faction::Red
-                }
+                faction: spawn.faction
             },
-            Inactive { timer: Timer::new(Duration::from_secs(3), TimerMode::Once) },
-            Health(100.0),
+            LifeStage::Larva,
+            (Hunger::default(), Options::default(), Decision::default()),
+            Health(stats.max_health),
+            stats,
+            genome,
+            record,
+            lineage_id,
+            Name::new(name),
+            SimPosition::new(pos.xy()),
             OnGameScreen,
             FollowPath {
                 end: pos.xy(),
                 done: true,
             },
-            Speed { speed: rng.gen_range(
-                game_data.bee_base_speed * (1.0 - RUMBLEBEE_SPEED_VARIANCE) ..
-                    game_data.bee_base_speed * (1.0 + RUMBLEBEE_SPEED_VARIANCE)) },
+            Speed { speed: stats.speed * LifeStage::Larva.speed_scale(&settings) },
             Bob,
             Displacement(Vec2 { x: 0., y: 0. }),
         )).id();
@@ -234,7 +336,7 @@ fn birth_a_bee(
         let arm = commands.spawn((
             SpriteSheetBundle {
                 texture_atlas: assets.arms.clone(),
-                transform: Transform::from_xyz(0.,0., 0.01),
+                transform: Transform::from_xyz(0.,0., 0.015),
                 ..default()
             },
             ArmAnim,
@@ -244,30 +346,8 @@ fn birth_a_bee(
             ),
         )).id();
 
-        let eyes = commands.spawn((
-            SpriteSheetBundle {
-                texture_atlas: assets.chars.clone(),
-                transform: Transform::from_xyz(0.,0., 0.01),
-                sprite: TextureAtlasSprite::new(9),
-                ..default()
-            },
-            AnimationIndices { frames: vec![9, 10, 11, 10], cur: 0 },
-            AnimationTimer(Timer::from_seconds(1.0, TimerMode::Repeating)),
-        )).id();
-
-        let wings = commands.spawn((
-            SpriteSheetBundle {
-                texture_atlas: assets.chars.clone(),
-                sprite: TextureAtlasSprite::new(6),
-                transform: Transform::from_xyz(0.,2., 0.01),
-                ..default()
-            },
-            AnimationIndices { frames: vec![6, 7, 8, 7], cur: 0 },
-            AnimationTimer(Timer::from_seconds(0.04, TimerMode::Repeating)),
-        )).id();
-
         // should be bee or bee_sprite?
-        commands.entity(bee).push_children(&[wings, arm, eyes]);
+        commands.entity(bee).push_children(&[torso, wings, arm, face, tag, decision]);
         if let Ok(beez) = parent.get_single() {
             commands.entity(beez).push_children(&[bee]);
         }
@@ -275,315 +355,91 @@ fn birth_a_bee(
 
 }
 
-/// Set the bee's pathfinding to go to a target tile
-fn find_target(
-    mut commands: Commands,
-    entity: Query<(Entity, &Transform, &RumbleBee),
-                  (Without<Inactive>, Without<Pathfinding>, Without<BeeFighter>)>,
-    tilemap: Query<(
-        &TilemapSize,
-        &TilemapGridSize,
-        &TilemapType,
-        &NavmeshPair,
-    )>,
-    eggs: Query<(&Egg, &TilePos)>,
-    mut game_data: ResMut<GameData>
-) {
-    let (map_size, grid_size, map_type, navmesh) = tilemap.single();
-    for entity in entity.iter() {
-        let pos = &entity.1
-            .translation.xy()
-            // TODO: 25 is bee size / 2. Get from transform!
-            .sub(Vec2 { x:GAP_LEFT + 25.0, y: 25.0 });
-        let Some(entity_pos) =
-            TilePos::from_world_pos(pos, map_size, grid_size, map_type)
-        else {
-            //Why are some not getting world pos?
-            info!("Entity outside map {:?} {} {}", &entity.1.translation.xy(), map_size.x as f32 * grid_size.x, map_size.y as f32 * grid_size.y);
-            continue;
-        };
-
-        let targets = eggs.iter().filter_map(|(egg, pos)| {
-            (match_faction(entity.2.faction, egg.faction)).then_some((egg, pos))
-        });
-
-        let mut target_path: Option<Pathfinding> = None;
-
-        /*
-        TODO: Choose an egg to target: just random, should be "closest"
-        1. for each target, look for a path. get the shortest.
-        2. if no target, check again for non-dirt path, choose shortest.
-        3. if none, wander.
-        */
-
-        if let Some(first) = targets.choose(&mut rand::thread_rng()) {
-            if let Some(path) = Pathfinding::astar(&navmesh.main, entity_pos, first.1.clone()) {
-                target_path = Some(path);
-            } else {
-                // TESTING: no digging now that bees age...
-                /*
-                // Find the neearest dirt tile that is blocking path to egg
-                if let Some(mut path) = Pathfinding::astar(&navmesh.alt, entity_pos, first.1.clone()) {
-                    // Get path to dirt
-                    let mut p: Vec<TilePos> = vec![];
-                    while path.step() {
-                        let pos = path.path[path.at];
-                        p.push(pos);
-                        if navmesh.main.solid(pos) {
-                            // hit dirt
-                            break;
-                        }
-                    }
-                    if p.len() > 0 {
-                        target_path = Some(Pathfinding { path: p, at: 0 });
-                    }
-                }
-                */
-            }
-        }
-
-        // No egg target, just wander to random spot
-        if target_path.is_none() {
-            // No target, just wander aimlessly
-            let mut rng = rand::thread_rng();
-            let mut ok = false;
-            let mut retries = 20;
-            while !ok {
-                if let Some(t)  = find_empty_tile(&navmesh.main, map_size) {
-                    if let Some(path) = Pathfinding::astar(&navmesh.main, entity_pos, t) {
-                        target_path = Some(path);
-                        ok = true;
-                    } else {
-                        // Found a free spot, but can't get to it.
-                        retries -= 1;
-                        if retries < 0 {
-                            // Not sure...
-                            //info!("No where to go!");
-                            ok = true;
-                        }
-                    }
-                } else {
-                    // No paths left. Game over?
-                    game_data.game_over = true;
-                    commands.spawn(GameOver);
-                }
-            }
-            if retries < 0 {
-                // Didn't find a path
-                continue;
-            }
-
-
-            let mut target = TilePos { x: 0, y: 0 };
-            while !ok {
-                target.x = rng.gen_range(0..map_size.x);
-                target.y = rng.gen_range(0..map_size.y);
-                if !&navmesh.main.solid(target) {
-                    if let Some(path) = Pathfinding::astar(&navmesh.main, entity_pos, target) {
-                        target_path = Some(path);
-                        ok = true;
-                    }
-                }
-            }
-        }
-
-        if let Some(path) = target_path {
-            commands.entity(entity.0).insert(path);
-        }
-
-    }
-}
-
-fn match_faction (a: Faction, b: Faction) -> bool {
-    a == b || b == Faction::Green
-}
-
 fn egg_collisions(
     mut commands: Commands,
-    beez: Query<(Entity, &RumbleBee, &Transform)>,
+    beez: Query<&RumbleBee>,
+    mut records: Query<&mut BeeRecord>,
+    grid: Res<BeeGrid>,
     mut eggs: Query<(Entity, &Egg, &mut Tile, &TilePos)>,
-    tilemap: Query<(&TileStorage, &TilemapSize, &TilemapGridSize)>,
-    mut tile_query: Query<&mut Tile, Without<Egg>>,
+    tilemap: Query<&TilemapGridSize>,
     mut got_egg_event: EventWriter<GotAnEgg>,
-    game_data: Res<GameData>
-){
-    let (tile_storage, map_size, grid_size) = tilemap.single();
-
-    for (_bee_ent, bee, bee_pos) in beez.iter() {
-        for (egg_ent, egg, mut egg_tile, egg_pos) in eggs.iter_mut() {
-            let pos = Vec3 {
-                x: egg_pos.x as f32 * grid_size.x + 25. + GAP_LEFT,
-                y: egg_pos.y as f32 * grid_size.y + 25.,
-                z: bee_pos.translation.z
-            };
-
-            if match_faction(bee.faction, egg.faction) &&
-                bee_pos.translation.distance(pos) < 20.0
-            {
-                // Got a egg..
-                commands.entity(egg_ent).remove::<Egg>();
-
-                if game_data.game_over {
-                    return;
-                }
-
-                got_egg_event.send_default();
-
-
-                // Egg turns into a un-passable stalk.
-                // Should it? Maybe just Air to make it easier
-                *egg_tile = Tile::Air;
-                //*egg_tile = Tile::Stalk { style: 2 };
-
-                // Spawn new bee
-                commands.spawn(BeeBorn {
-                    pos: Some(pos.xy().clone()),
-                    faction: bee.faction
-                });
-
-
-                let mut next_pos = egg_pos.clone();
-                let mut is_stalk = true;
-                let mut is_top = true;
-
-                // Walk down the stalk, turning the tree dead
-                while is_stalk {
-                    let down_pos = Neighbors::get_square_neighboring_positions(&next_pos, map_size, false).south;
-                    match down_pos {
-                        Some(down_pos) => {
-                            if let Some(plant_ent) = tile_storage.get(&down_pos) {
-                                if let Ok(mut tile) = tile_query.get_mut(plant_ent) {
-                                    match &mut *tile {
-                                        Tile::Stalk { style } => {
-                                            if *style == 0 {
-                                                *style = if is_top { 2 } else { 1 };
-                                                is_top = false;
-                                            }
-                                            next_pos = down_pos;
-                                        },
-                                        _ => {
-                                            is_stalk = false;
-                                            // Is it only a 1-high tree?
-                                            if is_top {
-                                                // Yep, make it a dead stalk (instead of air)
-                                                // good rule? Otherwise making 1-high would
-                                                // be dominant strat
-                                                *egg_tile = Tile::Stalk { style: 2 };
-                                            }
-
-                                        },
-                                    }
-                                }
-                            }
-                        },
-                        None => {
-                            is_stalk = false;
-                        }
-                    }}
-            }
-        }
-    }
-}
-
-fn fight_collisions(
-    mut commands: Commands,
-    beez: Query<(Entity, &RumbleBee, &Transform), (Without<Inactive>, Without<BeeFighter>)>,
-    time: Res<Time>
+    diplomacy: Res<Diplomacy>,
+    game_data: Res<GameData>,
+    settings: Res<GameSettings>
 ){
-    for [
-        (ent_a, bee_a, pos_a),
-        (ent_b, bee_b, pos_b)
-    ] in beez.iter_combinations() {
-        if bee_a.faction == bee_b.faction {
-            continue;
+    let grid_size = tilemap.single();
+
+    for (egg_ent, egg, mut egg_tile, egg_pos) in eggs.iter_mut() {
+        let pos = tilepos_to_px(egg_pos, grid_size);
+        let collector = grid
+            .within(pos, settings.egg_collect_radius)
+            .filter_map(|(ent, _)| beez.get(ent).ok().map(|bee| (ent, bee)))
+            .find(|(_, bee)| diplomacy.can_collect(bee.faction, egg.faction));
+        let Some((bee_ent, bee)) = collector else { continue };
+        if let Ok(mut record) = records.get_mut(bee_ent) {
+            record.eggs += 1;
         }
-        let a = pos_a.translation.xy();
-        let b = pos_b.translation.xy();
-        if a.distance(b) < 50.0 {
-            // GET READY TO BRUMBLE!
-            commands.entity(ent_a).insert(BeeFighter);
-            commands.entity(ent_b).insert(BeeFighter);
-            commands.spawn(BeeFight {
-                bee1: ent_a,
-                bee2: ent_b,
-                started: time.last_update().unwrap()
-            });
+        let mate = grid
+            .within(pos, settings.mate_radius)
+            .filter(|(ent, _)| *ent != bee_ent)
+            .filter(|(ent, _)| beez.get(*ent).is_ok_and(|other| other.faction == bee.faction))
+            .min_by(|(_, a), (_, b)| a.distance(pos).total_cmp(&b.distance(pos)))
+            .map(|(ent, _)| ent);
+
+        // Got a egg..
+        commands.entity(egg_ent).remove::<Egg>();
+
+        if game_data.game_over {
+            return;
         }
-    }
-}
 
-fn became_a_fighter(
-    mut commands: Commands,
-    beez: Query<(Entity, &RumbleBee, &Transform, &Children), Added<BeeFighter>>,
-    mut arms: Query<&mut AnimationIndices, With<ArmAnim>>,
-){
-    // Bee just got in a fight.
-    for (ent, _bee, _transform, children) in beez.iter() {
-        commands
-            .entity(ent)
-            .remove::<Pathfinding>();
-
-        for child in children.iter() {
-            if let Ok(mut arm) = arms.get_mut(*child) {
-                arm.frames = vec![0, 1];
-                arm.cur = 0;
-            }
-        }
+        got_egg_event.send_default();
 
-    }
-}
 
-fn bee_fight(
-    mut commands: Commands,
-    mut bee_fight: Query<(Entity, &mut BeeFight)>,
-    bees: Query<(Entity, &Children), With<BeeFighter>>,
-    mut arms: Query<&mut AnimationIndices, With<ArmAnim>>,
-    time: Res<Time>,
-){
-    for (fight_ent, beefight) in bee_fight.iter_mut() {
-        let t = time.last_update().unwrap() - beefight.started;
-        if t.as_secs() < 5 {
-            continue;
-        }
-        commands.entity(fight_ent).despawn();
-
-        for (bee, kids) in bees.iter() {
-            if bee == beefight.bee1 {
-                commands
-                    .entity(bee)
-                    .remove::<BeeFighter>();
-
-                for &child in kids.iter() {
-                    if let Ok(mut arm) = arms.get_mut(child) {
-                        // Back to no anim
-                        arm.frames = vec![0];
-                        arm.cur = 0;
-                    }
-                }
-            }
-            if bee == beefight.bee2 {
-                commands.entity(beefight.bee2).insert(BeeKilled);
-            }
-        }
+        *egg_tile = Tile::Air;
+
+        // Spawn new bee. An ally's egg hatches one of theirs.
+        commands.spawn(BeeBorn {
+            pos: Some(pos),
+            faction: if egg.faction == Faction::Green { bee.faction } else { egg.faction },
+            parent: Some(bee_ent),
+            mate,
+        });
 
+        // The plant sees its egg is gone, and wilts.
     }
 }
 
 fn bee_dead(
     mut commands: Commands,
-    mut ent: Query<(Entity, &Transform), Added<BeeKilled>>,
+    mut ent: Query<
+        (Entity, &SimPosition, Option<&LineageId>, Option<&BeeRecord>),
+        Added<BeeKilled>,
+    >,
     all_beez: Query<&RumbleBee, Without<BeeKilled>>,
     tilemap: Query<&TilemapGridSize>,
     mut game_data: ResMut<GameData>,
-    assets: Res<AssetCol>
+    mut lineage: ResMut<Lineage>,
+    assets: Res<AssetCol>,
+    settings: Res<GameSettings>,
 ) {
-    for (ent, pos) in ent.iter_mut() {
+    for (ent, pos, lineage_id, record) in ent.iter_mut() {
 
         let grid_size = tilemap.single();
         commands.entity(ent).despawn_recursive();
 
+        // Gone before update_lineage might see it, so sign them off here
+        if let Some(entry) = lineage_id.and_then(|id| lineage.bees.get_mut(id.0)) {
+            entry.alive = false;
+            if let Some(record) = record {
+                entry.age = record.age;
+                entry.kills = record.kills;
+                entry.eggs = record.eggs;
+            }
+        }
+
         // Get tile pos.
-        let tp = px_to_tilepos(pos.translation.xy().sub(Vec2 { x: GAP_LEFT, y: 0.0 }), grid_size);
+        let tp = px_to_tilepos(pos.pos.sub(Vec2 { x: GAP_LEFT, y: 0.0 }), grid_size);
         let tpx = tilepos_to_px(&tp, grid_size);
 
         commands.spawn((SpriteSheetBundle {
@@ -618,18 +474,24 @@ fn bee_dead(
             ..default()
     }, OnGameScreen));*/
 
-        // Is it game over?
-        let mut blue = 0;
-        let mut red = 0;
-        for bee in all_beez.iter() {
-            if bee.faction == Faction::Blue {
-                blue += 1;
+        // Knock out any faction with no bees left
+        for faction in &settings.level.factions {
+            if game_data.eliminated.contains(faction) {
+                continue;
             }
-            if bee.faction == Faction::Red {
-                red += 1;
+            if !all_beez.iter().any(|bee| bee.faction == *faction) {
+                game_data.eliminated.push(*faction);
+                screen_print!(col: faction.color(), "{:?} ELIMINATED", faction);
             }
         }
-        if (blue == 0 || red == 0) &&
+
+        // Is it game over?
+        // Counted from the level, which a settings reload can change
+        let remaining = settings.level.factions
+            .iter()
+            .filter(|faction| !game_data.eliminated.contains(faction))
+            .count();
+        if remaining <= 1 &&
             !game_data.game_over
         {
             // Game over!
@@ -643,40 +505,20 @@ fn bee_dead(
 fn get_older(
     mut commands: Commands,
     mut beez: Query<
-            (Entity, &mut Health, &mut Children, Option<&OldTimer>),
+            (Entity, &mut Health, &mut BeeRecord, &BeeStats),
         (With<RumbleBee>, Without<BeeKilled>)>,
-    time: Res<Time>,
-    assets: Res<AssetCol>,
+    fixed_time: Res<FixedTime>,
     game_data: Res<GameData>,
+    settings: Res<GameSettings>,
 
 ) {
     if game_data.game_over {
         return;
     }
 
-    for (ent, mut health, _children, oldy) in beez.iter_mut() {
-        health.0 = health.0.sub(AGING_AMOUNT_PER_TICK * time.delta_seconds());
-
-        // Add or remove beard
-        if oldy.is_none() {
-            if  health.0 < 20.0 {
-                // Get a beard
-                let beard = commands.spawn(
-                    SpriteSheetBundle {
-                        texture_atlas: assets.chars.clone(),
-                        transform: Transform::from_xyz(0.,0., 0.01),
-                        sprite: TextureAtlasSprite::new(15),
-                        ..default()
-                    },
-                ).id();
-                commands.entity(ent).insert(OldTimer).push_children(&[beard]);
-            }
-        } else {
-            if health.0 > 20.0 {
-                // Lose a beard!
-                //commands.entity(ent).remove::<OldTimer>();
-            }
-        }
+    for (ent, mut health, mut record, stats) in beez.iter_mut() {
+        record.age += sim_delta(&fixed_time);
+        health.0 = health.0.sub(settings.aging_amount_per_tick * stats.aging * sim_delta(&fixed_time));
 
         if health.0 <= 0.0 {
             commands.entity(ent).insert(BeeKilled);
@@ -0,0 +1,386 @@
+//! Utility AI. When a bee has nothing to do, every behaviour scores itself
+//! from 0 to 1 for that bee and offers a plan, and the bee goes with the
+//! best offer until the plan runs out. Scores are in `BeeAi::Score`, one
+//! system per behaviour, so a new behaviour is a new system in there.
+//!
+//! Scoring is cheap: a plan only says where the bee wants to go. The path
+//! is worked out in `decide`, for the best offer only, and if there's no
+//! way there the next best gets a go.
+//!
+//! Press B to show what each bee is up to. It starts on in debug builds.
+use std::ops::Sub;
+
+use rand::seq::IteratorRandom;
+
+use crate::game::{NavmeshPair, Stuck, Upright};
+use crate::prelude::*;
+use crate::rumblebees::brawl::BeeFighter;
+use crate::rumblebees::{Inactive, RumbleBee};
+use crate::simulation::{SimPosition, SimSet};
+use crate::terrain::GAP_LEFT;
+use crate::GameState;
+
+pub mod eggs;
+pub mod feed;
+pub mod fight;
+pub mod rest;
+pub mod wander;
+
+use rest::Resting;
+
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BeeAi {
+    /// Behaviours add their `Candidate`s to idle bees' `Options`.
+    Score,
+    /// Idle bees take their best option.
+    Decide,
+}
+
+/// Bees with nothing to do. Only these are scored.
+pub type Idle = (
+    With<RumbleBee>,
+    Without<Pathfinding>,
+    Without<BeeFighter>,
+    Without<Inactive>,
+    Without<Stuck>,
+    Without<Resting>,
+);
+
+const WANDER_TRIES: usize = 20; // Free spots to try a path to
+const FLEE_TRIES: usize = 8; // Free spots to pick the furthest from
+
+/// What a behaviour will do if the bee picks it.
+#[derive(Debug)]
+pub enum Plan {
+    GoTo(TilePos),
+    GoToNearest(Vec<TilePos>), // Closest first, goes to the first it can reach
+    DigTo(TilePos),            // Only as far as the first dirt on the way
+    Flee(TilePos),             // From this tile
+    Wander,
+    Rest(f32), // Seconds to stay put
+}
+
+/// A `Plan` worked out for where the bee is now.
+#[derive(Debug)]
+pub enum Act {
+    Path(Pathfinding),
+    Rest(f32),
+}
+
+#[derive(Debug)]
+pub struct Candidate {
+    pub behaviour: &'static str,
+    pub score: f32,
+    pub plan: Plan,
+}
+
+/// This step's offers for an idle bee.
+#[derive(Component, Debug, Default)]
+pub struct Options(Vec<Candidate>);
+impl Options {
+    pub fn offer(&mut self, behaviour: &'static str, score: f32, plan: Plan) {
+        self.0.push(Candidate { behaviour, score: score.clamp(0.0, 1.0), plan });
+    }
+}
+
+/// What the bee last decided to do, and how every behaviour scored.
+#[derive(Component, Debug, Default, Clone)]
+pub struct Decision {
+    pub behaviour: &'static str,
+    pub score: f32,
+    pub scores: Vec<(&'static str, f32)>, // Best first
+}
+
+/// Show each bee's `Decision` under it.
+#[derive(Resource, Debug)]
+pub struct ShowDecisions(pub bool);
+impl Default for ShowDecisions {
+    fn default() -> Self {
+        Self(cfg!(debug_assertions))
+    }
+}
+
+/// The label under a bee with its `Decision`.
+#[derive(Component)]
+pub struct DecisionTag;
+impl DecisionTag {
+    #[must_use]
+    pub fn new(font: Handle<Font>) -> (Self, Upright, Text2dBundle) {
+        let bundle = Text2dBundle {
+            text: Text::from_section("", TextStyle { font, font_size: 18.0, color: Color::WHITE }),
+            transform: Transform::from_xyz(0.0, -48.0, 1.0),
+            ..default()
+        };
+        (Self, Upright, bundle)
+    }
+}
+
+pub struct BehaviourPlugin;
+impl Plugin for BehaviourPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ShowDecisions>()
+            .configure_sets(
+                FixedUpdate,
+                (BeeAi::Score, BeeAi::Decide).chain().in_set(SimSet::Simulate),
+            )
+            .add_systems(
+                FixedUpdate,
+                (
+                    eggs::collect_egg,
+                    eggs::dig_to_egg,
+                    feed::feed,
+                    fight::fight,
+                    fight::flee,
+                    rest::rest,
+                    wander::wander,
+                ).in_set(BeeAi::Score),
+            )
+            .add_systems(
+                FixedUpdate,
+                (
+                    (decide, forget_options).chain().in_set(BeeAi::Decide),
+                    rest::recover.in_set(SimSet::Simulate),
+                ),
+            )
+            .add_systems(
+                Update,
+                (toggle_decisions, show_decisions).run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+/// The tile a bee is in, if it's on the map.
+#[must_use]
+pub fn bee_tile(
+    pos: Vec2,
+    map_size: &TilemapSize,
+    grid_size: &TilemapGridSize,
+    map_type: &TilemapType,
+) -> Option<TilePos> {
+    // TODO: 25 is bee size / 2. Get from transform!
+    let pos = pos.sub(Vec2 { x: GAP_LEFT + 25.0, y: 25.0 });
+    TilePos::from_world_pos(&pos, map_size, grid_size, map_type)
+}
+
+/// Every tile a bee can fly through.
+fn free_tiles(navmesh: &Navmesh, map_size: &TilemapSize) -> Vec<TilePos> {
+    (0..map_size.x)
+        .flat_map(|x| (0..map_size.y).map(move |y| TilePos { x, y }))
+        .filter(|pos| !navmesh.solid(*pos))
+        .collect()
+}
+
+/// Turn a plan into something to do from `from`, if it can be done.
+fn work_out(
+    plan: &Plan,
+    from: TilePos,
+    navmesh: &NavmeshPair,
+    map_size: &TilemapSize,
+) -> Option<Act> {
+    let mut rng = rand::thread_rng();
+    let path = match plan {
+        Plan::GoTo(to) => Pathfinding::astar(&navmesh.main, from, *to),
+        Plan::GoToNearest(tos) => {
+            tos.iter().find_map(|to| Pathfinding::astar(&navmesh.main, from, *to))
+        }
+        Plan::DigTo(to) => {
+            // Getting stuck in the dirt starts the digging
+            let mut path = Pathfinding::astar(&navmesh.alt, from, *to)?;
+            let mut to_dirt = vec![];
+            loop {
+                if !path.step() {
+                    return None;
+                }
+                let pos = path.path[path.at];
+                to_dirt.push(pos);
+                if navmesh.main.solid(pos) {
+                    break;
+                }
+            }
+            Some(Pathfinding { path: to_dirt, at: 0 })
+        }
+        Plan::Flee(threat) => {
+            let away = |pos: &TilePos| pos.x.abs_diff(threat.x) + pos.y.abs_diff(threat.y);
+            free_tiles(&navmesh.main, map_size)
+                .into_iter()
+                .filter(|to| away(to) > away(&from))
+                .choose_multiple(&mut rng, FLEE_TRIES)
+                .into_iter()
+                .max_by_key(away)
+                .and_then(|to| Pathfinding::astar(&navmesh.main, from, to))
+        }
+        Plan::Wander => free_tiles(&navmesh.main, map_size)
+            .into_iter()
+            .choose_multiple(&mut rng, WANDER_TRIES)
+            .into_iter()
+            // Found a free spot, but might not get to it
+            .find_map(|to| Pathfinding::astar(&navmesh.main, from, to)),
+        Plan::Rest(secs) => return Some(Act::Rest(*secs)),
+    };
+    path.map(Act::Path)
+}
+
+/// The best candidate that works out, and how they all scored.
+fn pick(
+    mut candidates: Vec<Candidate>,
+    mut work_out: impl FnMut(&Plan) -> Option<Act>,
+) -> Option<(Decision, Act)> {
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+    let scores: Vec<_> = candidates.iter().map(|c| (c.behaviour, c.score)).collect();
+    candidates.into_iter().find_map(|c| {
+        let act = work_out(&c.plan)?;
+        let decision = Decision { behaviour: c.behaviour, score: c.score, scores: scores.clone() };
+        Some((decision, act))
+    })
+}
+
+fn decide(
+    mut commands: Commands,
+    mut beez: Query<(Entity, &SimPosition, &mut Options, &mut Decision), Idle>,
+    tilemap: Query<(&TilemapSize, &TilemapGridSize, &TilemapType, &NavmeshPair)>,
+) {
+    let (map_size, grid_size, map_type, navmesh) = tilemap.single();
+    for (ent, sim, mut options, mut decision) in &mut beez {
+        let candidates = std::mem::take(&mut options.0);
+        let Some(from) = bee_tile(sim.pos, map_size, grid_size, map_type) else { continue };
+        let Some((picked, act)) = pick(candidates, |plan| work_out(plan, from, navmesh, map_size))
+        else {
+            continue;
+        };
+        *decision = picked;
+        match act {
+            Act::Path(path) => {
+                commands.entity(ent).insert(path);
+            }
+            Act::Rest(secs) => {
+                commands.entity(ent).insert(Resting(Timer::from_seconds(secs, TimerMode::Once)));
+            }
+        }
+    }
+}
+
+/// Drop offers to bees that stopped being idle before they could decide,
+/// so they don't carry over to the next time they are.
+fn forget_options(mut beez: Query<&mut Options>) {
+    for mut options in &mut beez {
+        if !options.0.is_empty() {
+            options.0.clear();
+        }
+    }
+}
+
+fn toggle_decisions(
+    keys: Res<Input<KeyCode>>,
+    mut show: ResMut<ShowDecisions>,
+) {
+    if keys.just_pressed(KeyCode::B) {
+        show.0 = !show.0;
+    }
+}
+
+fn show_decisions(
+    beez: Query<(Ref<Decision>, &Children), With<RumbleBee>>,
+    mut tags: Query<(&mut Text, &mut Visibility), With<DecisionTag>>,
+    show: Res<ShowDecisions>,
+) {
+    for (decision, kids) in &beez {
+        for &kid in kids.iter() {
+            let Ok((mut text, mut visibility)) = tags.get_mut(kid) else { continue };
+            let wanted = if show.0 { Visibility::Inherited } else { Visibility::Hidden };
+            if *visibility != wanted {
+                *visibility = wanted;
+            }
+            if show.0 && (decision.is_changed() || show.is_changed()) {
+                text.sections[0].value = format!("{} {:.2}", decision.behaviour, decision.score);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Each offer rests for its place in the list, so we can tell which won.
+    fn options(offers: &[(&'static str, f32)]) -> Vec<Candidate> {
+        let mut options = Options::default();
+        for (secs, &(behaviour, score)) in (0u8..).zip(offers) {
+            options.offer(behaviour, score, Plan::Rest(f32::from(secs)));
+        }
+        options.0
+    }
+
+    fn rest_for(plan: &Plan) -> Option<Act> {
+        match plan {
+            Plan::Rest(secs) => Some(Act::Rest(*secs)),
+            _ => None,
+        }
+    }
+
+    fn rested(act: &Act) -> f32 {
+        match act {
+            Act::Rest(secs) => *secs,
+            Act::Path(_) => panic!("expected a rest"),
+        }
+    }
+
+    #[test]
+    fn test_offer_clamps() {
+        let candidates = options(&[("a", 3.0), ("b", -1.0), ("c", 0.4)]);
+        let scores: Vec<f32> = candidates.iter().map(|c| c.score).collect();
+        for (score, want) in scores.into_iter().zip([1.0, 0.0, 0.4]) {
+            assert!((score - want).abs() < 1e-5, "{score} != {want}");
+        }
+    }
+
+    #[test]
+    fn test_best_wins() {
+        let candidates = options(&[("wander", 0.1), ("feed", 0.8), ("fight", 0.5)]);
+        let (decision, act) = pick(candidates, rest_for).unwrap();
+        assert_eq!(decision.behaviour, "feed");
+        assert!((decision.score - 0.8).abs() < 1e-5);
+        let order: Vec<_> = decision.scores.iter().map(|(behaviour, _)| *behaviour).collect();
+        assert_eq!(order, ["feed", "fight", "wander"]);
+        assert!((rested(&act) - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_falls_back() {
+        let candidates = options(&[("wander", 0.1), ("feed", 0.8), ("fight", 0.5)]);
+        // No way to get to the food
+        let (decision, act) = pick(candidates, |plan| match plan {
+            Plan::Rest(secs) if *secs > 0.5 && *secs < 1.5 => None,
+            plan => rest_for(plan),
+        })
+        .unwrap();
+        assert_eq!(decision.behaviour, "fight");
+        assert_eq!(decision.scores.len(), 3);
+        assert!((rested(&act) - 2.0).abs() < 1e-5);
+    }
+
+    /// Every plan works out, so only the scores matter.
+    fn anything(plan: &Plan) -> Option<Act> {
+        rest_for(plan).or_else(|| Some(Act::Path(Pathfinding { path: vec![], at: 0 })))
+    }
+
+    #[test]
+    fn test_worn_out_rests() {
+        let choose = |health| {
+            let mut options = Options::default();
+            let egg = Plan::GoTo(TilePos { x: 3, y: 4 });
+            options.offer("collect egg", eggs::collect_score(health), egg);
+            options.offer("rest", rest::rest_score(health), Plan::Rest(3.0));
+            options.offer("wander", 0.1, Plan::Wander);
+            pick(options.0, anything).unwrap().0.behaviour
+        };
+        assert_eq!(choose(0.05), "rest");
+        assert_eq!(choose(1.0), "collect egg");
+    }
+
+    #[test]
+    fn test_nothing_to_do() {
+        assert!(pick(vec![], rest_for).is_none());
+        assert!(pick(options(&[("feed", 0.8)]), |_| None).is_none());
+    }
+}
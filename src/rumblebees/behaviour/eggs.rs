@@ -0,0 +1,57 @@
+//! Going for eggs: fly to one, or dig towards one that's walled in.
+use rand::seq::IteratorRandom;
+
+use crate::diplomacy::Diplomacy;
+use crate::game::Health;
+use crate::prelude::*;
+use crate::rumblebees::behaviour::{Idle, Options, Plan};
+use crate::rumblebees::{BeeStats, RumbleBee};
+use crate::terrain::Egg;
+
+const COLLECT_SCORE: f32 = 0.5; // For a healthy bee, half that for a dying one
+const DIG_SCORE: f32 = 0.3; // For a healthy bee. Digging takes a while
+
+/*
+TODO: Choose an egg to target: just random, should be "closest"
+1. for each target, look for a path. get the shortest.
+*/
+
+/// How much a bee with `health` of its max (0..1) wants an egg.
+#[must_use]
+pub fn collect_score(health: f32) -> f32 {
+    COLLECT_SCORE * (0.5 + 0.5 * health)
+}
+
+pub fn collect_egg(
+    mut beez: Query<(&RumbleBee, &Health, &BeeStats, &mut Options), Idle>,
+    eggs: Query<(&Egg, &TilePos)>,
+    diplomacy: Res<Diplomacy>,
+) {
+    for (bee, health, stats, mut options) in &mut beez {
+        let target = eggs
+            .iter()
+            .filter(|(egg, _)| diplomacy.can_collect(bee.faction, egg.faction))
+            .choose(&mut rand::thread_rng());
+        let Some((_, to)) = target else { continue };
+        let score = collect_score(health.0 / stats.max_health);
+        options.offer("collect egg", score, Plan::GoTo(*to));
+    }
+}
+
+/// Head for the first dirt between the bee and an egg. Scores below
+/// collecting, so it's what the bee does when it can't fly there.
+pub fn dig_to_egg(
+    mut beez: Query<(&RumbleBee, &Health, &BeeStats, &mut Options), Idle>,
+    eggs: Query<(&Egg, &TilePos)>,
+    diplomacy: Res<Diplomacy>,
+) {
+    for (bee, health, stats, mut options) in &mut beez {
+        let target = eggs
+            .iter()
+            .filter(|(egg, _)| diplomacy.can_collect(bee.faction, egg.faction))
+            .choose(&mut rand::thread_rng());
+        let Some((_, to)) = target else { continue };
+        let score = DIG_SCORE * (health.0 / stats.max_health);
+        options.offer("dig", score, Plan::DigTo(*to));
+    }
+}
@@ -0,0 +1,36 @@
+//! Hungry bees go for the nearest food. The hungrier, the more it matters.
+use crate::prelude::*;
+use crate::rumblebees::behaviour::{Idle, Options, Plan, bee_tile};
+use crate::rumblebees::needs::{self, Hunger};
+use crate::settings::GameSettings;
+use crate::simulation::SimPosition;
+use crate::terrain::Tile;
+
+const TRIES: usize = 5; // Nearest food tiles to try a path to
+
+pub fn feed(
+    mut beez: Query<(&SimPosition, &Hunger, &mut Options), Idle>,
+    tilemap: Query<(&TilemapSize, &TilemapGridSize, &TilemapType, &TileStorage)>,
+    tiles: Query<&Tile>,
+    settings: Res<GameSettings>,
+) {
+    let (map_size, grid_size, map_type, storage) = tilemap.single();
+    // Only worked out if someone's hungry
+    let mut food: Option<Vec<TilePos>> = None;
+    for (sim, hunger, mut options) in &mut beez {
+        if !hunger.is_hungry(&settings) {
+            continue;
+        }
+        let Some(from) = bee_tile(sim.pos, map_size, grid_size, map_type) else { continue };
+        let food = food.get_or_insert_with(|| needs::food_tiles(storage, map_size, &tiles));
+        let mut nearest = food.clone();
+        nearest.sort_by_key(|pos| pos.x.abs_diff(from.x) + pos.y.abs_diff(from.y));
+        nearest.truncate(TRIES);
+        if nearest.is_empty() {
+            continue;
+        }
+        // Just hungry beats an egg, starving beats nearly everything
+        let score = 0.6 + 0.4 * hunger.0 / Hunger::STARVING;
+        options.offer("feed", score, Plan::GoToNearest(nearest));
+    }
+}
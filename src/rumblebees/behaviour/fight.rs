@@ -0,0 +1,85 @@
+//! Picking fights and running from them. Strong, healthy bees go looking
+//! for nearby enemies; hurt bees get away from them. The brawl itself
+//! starts when they get close (brawl.rs).
+use crate::diplomacy::Diplomacy;
+use crate::game::Health;
+use crate::prelude::*;
+use crate::rumblebees::behaviour::{Idle, Options, Plan, bee_tile};
+use crate::rumblebees::life::LifeStage;
+use crate::rumblebees::spatial::BeeGrid;
+use crate::rumblebees::{BeeStats, RumbleBee};
+use crate::settings::GameSettings;
+use crate::simulation::SimPosition;
+use crate::terrain::Faction;
+
+const FIGHT_SCORE: f32 = 0.6; // For a full health bee at normal strength, right by an enemy
+const FLEE_SCORE: f32 = 0.95; // For a bee on its last legs
+
+/// The closest enemy the bee can see.
+fn nearest_enemy(
+    grid: &BeeGrid,
+    pos: Vec2,
+    faction: Faction,
+    others: &Query<&RumbleBee>,
+    diplomacy: &Diplomacy,
+    radius: f32,
+) -> Option<Vec2> {
+    grid.within(pos, radius)
+        .filter(|(ent, _)| others.get(*ent).is_ok_and(|o| diplomacy.will_fight(faction, o.faction)))
+        .map(|(_, other)| other)
+        .min_by(|a, b| a.distance(pos).total_cmp(&b.distance(pos)))
+}
+
+pub fn fight(
+    mut beez: Query<
+        (&SimPosition, &RumbleBee, &LifeStage, &Health, &BeeStats, &mut Options),
+        Idle,
+    >,
+    others: Query<&RumbleBee>,
+    tilemap: Query<(&TilemapSize, &TilemapGridSize, &TilemapType)>,
+    grid: Res<BeeGrid>,
+    diplomacy: Res<Diplomacy>,
+    settings: Res<GameSettings>,
+) {
+    let (map_size, grid_size, map_type) = tilemap.single();
+    for (sim, bee, stage, health, stats, mut options) in &mut beez {
+        if !stage.can_fight() || health.0 < settings.retreat_health {
+            continue;
+        }
+        let radius = settings.sight_radius;
+        let Some(enemy) = nearest_enemy(&grid, sim.pos, bee.faction, &others, &diplomacy, radius)
+        else {
+            continue;
+        };
+        let Some(to) = bee_tile(enemy, map_size, grid_size, map_type) else { continue };
+        let close = 1.0 - sim.pos.distance(enemy) / radius;
+        let score = FIGHT_SCORE * stats.strength * (health.0 / stats.max_health) * close;
+        options.offer("fight", score, Plan::GoTo(to));
+    }
+}
+
+pub fn flee(
+    mut beez: Query<(&SimPosition, &RumbleBee, &LifeStage, &Health, &mut Options), Idle>,
+    others: Query<&RumbleBee>,
+    tilemap: Query<(&TilemapSize, &TilemapGridSize, &TilemapType)>,
+    grid: Res<BeeGrid>,
+    diplomacy: Res<Diplomacy>,
+    settings: Res<GameSettings>,
+) {
+    let (map_size, grid_size, map_type) = tilemap.single();
+    for (sim, bee, stage, health, mut options) in &mut beez {
+        // Larvae can't fight back, so they're wary
+        let weak = if stage.can_fight() { health.0 / settings.retreat_health } else { 0.5 };
+        if weak >= 1.0 {
+            continue;
+        }
+        let radius = settings.sight_radius;
+        let Some(enemy) = nearest_enemy(&grid, sim.pos, bee.faction, &others, &diplomacy, radius)
+        else {
+            continue;
+        };
+        let Some(threat) = bee_tile(enemy, map_size, grid_size, map_type) else { continue };
+        let score = FLEE_SCORE * (1.0 - 0.5 * weak);
+        options.offer("flee", score, Plan::Flee(threat));
+    }
+}
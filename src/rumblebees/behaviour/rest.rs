@@ -0,0 +1,49 @@
+//! Resting. A worn out bee with nothing pressing stays put for a bit and
+//! gets some health back. It can still be picked on while it does.
+use crate::game::Health;
+use crate::prelude::*;
+use crate::rumblebees::behaviour::{Idle, Options, Plan};
+use crate::rumblebees::needs::Hunger;
+use crate::rumblebees::{BeeKilled, BeeStats};
+use crate::settings::GameSettings;
+use crate::simulation::sim_delta;
+
+const SCORE: f32 = 0.4; // For a bee with no health left
+
+/// Staying put until the timer runs out.
+#[derive(Component)]
+pub struct Resting(pub Timer);
+
+/// How much a bee with `health` of its max (0..1) wants a rest.
+#[must_use]
+pub fn rest_score(health: f32) -> f32 {
+    SCORE * (1.0 - health)
+}
+
+pub fn rest(
+    mut beez: Query<(&Health, &BeeStats, &Hunger, &mut Options), Idle>,
+    settings: Res<GameSettings>,
+) {
+    for (health, stats, hunger, mut options) in &mut beez {
+        if hunger.is_hungry(&settings) {
+            continue;
+        }
+        let score = rest_score(health.0 / stats.max_health);
+        options.offer("rest", score, Plan::Rest(settings.rest_secs));
+    }
+}
+
+pub fn recover(
+    mut commands: Commands,
+    mut beez: Query<(Entity, &mut Resting, &mut Health, &BeeStats), Without<BeeKilled>>,
+    fixed_time: Res<FixedTime>,
+    settings: Res<GameSettings>,
+) {
+    for (ent, mut resting, mut health, stats) in &mut beez {
+        let heal = settings.rest_heal_per_sec * sim_delta(&fixed_time);
+        health.0 = (health.0 + heal).min(stats.max_health);
+        if resting.0.tick(fixed_time.period).finished() {
+            commands.entity(ent).remove::<Resting>();
+        }
+    }
+}
@@ -0,0 +1,37 @@
+//! Wander to a random free spot. Always on offer, so a bee never stands
+//! around with nothing better to do.
+use crate::game::{GameData, GameOver, NavmeshPair};
+use crate::prelude::*;
+use crate::rumblebees::behaviour::{Idle, Options, Plan, bee_tile};
+use crate::simulation::SimPosition;
+use crate::terrain::find_empty_tile;
+
+const SCORE: f32 = 0.1;
+
+pub fn wander(
+    mut commands: Commands,
+    mut beez: Query<(&SimPosition, &mut Options), Idle>,
+    tilemap: Query<(&TilemapSize, &TilemapGridSize, &TilemapType, &NavmeshPair)>,
+    mut game_data: ResMut<GameData>,
+) {
+    if beez.is_empty() {
+        return;
+    }
+    let (map_size, grid_size, map_type, navmesh) = tilemap.single();
+    if find_empty_tile(&navmesh.main, map_size).is_none() {
+        // No paths left. Game over?
+        if !game_data.game_over {
+            game_data.game_over = true;
+            commands.spawn(GameOver);
+        }
+        return;
+    }
+    for (sim, mut options) in &mut beez {
+        if bee_tile(sim.pos, map_size, grid_size, map_type).is_none() {
+            //Why are some not getting world pos?
+            info!("Entity outside map {:?}", sim.pos);
+            continue;
+        }
+        options.offer("wander", SCORE, Plan::Wander);
+    }
+}
@@ -0,0 +1,326 @@
+//! Brawls. Hostile bees that get close start a fight, and any other bee
+//! that gets close to a fighter and is hostile to them piles in. Fights
+//! that touch merge, so a bee is only ever in one.
+use bevy::utils::HashMap;
+use rand::Rng;
+
+use crate::diplomacy::Diplomacy;
+use crate::game::{AnimationIndices, Health, OnGameScreen, Speed};
+use crate::prelude::*;
+use crate::rumblebees::{ArmAnim, BeeKilled, BeeRecord, BeeStats, Inactive, RumbleBee};
+use crate::rumblebees::life::LifeStage;
+use crate::rumblebees::spatial::BeeGrid;
+use crate::settings::GameSettings;
+use crate::simulation::SimPosition;
+use crate::terrain::Faction;
+use crate::{AssetCol, Layers};
+
+#[derive(Component)]
+pub struct BeeFight {
+    members: Vec<Entity>,
+    timer: Timer, // Give up when it runs out
+    punch_timer: Timer,
+    dealt: HashMap<(Entity, Entity), f32>, // Damage done to a bee, by each attacker
+}
+
+/// The fight a bee is in.
+#[derive(Component)]
+pub struct BeeFighter {
+    pub fight: Entity,
+}
+
+/// Arm is mid-punch. Pulled back when the timer runs out.
+#[derive(Component)]
+pub struct Punching(Timer);
+
+/// Union-find root, with path halving.
+fn root(groups: &mut [usize], mut i: usize) -> usize {
+    while groups[i] != i {
+        groups[i] = groups[groups[i]];
+        i = groups[i];
+    }
+    i
+}
+
+pub fn fight_collisions(
+    mut commands: Commands,
+    beez: Query<
+        (Entity, &RumbleBee, &LifeStage, &SimPosition, Option<&BeeFighter>),
+        Without<Inactive>,
+    >,
+    mut fights: Query<&mut BeeFight>,
+    grid: Res<BeeGrid>,
+    diplomacy: Res<Diplomacy>,
+    assets: Res<AssetCol>,
+    settings: Res<GameSettings>,
+){
+    // A fight that's ended or dropped the bee doesn't count
+    let in_fight = |ent: Entity, fighter: Option<&BeeFighter>| {
+        fighter
+            .map(|f| f.fight)
+            .filter(|fight| fights.get(*fight).is_ok_and(|f| f.members.contains(&ent)))
+    };
+    let bees: Vec<_> = beez
+        .iter()
+        .filter(|(_, _, stage, ..)| stage.can_fight())
+        .map(|(ent, bee, _, sim, fighter)| (ent, bee.faction, sim.pos, in_fight(ent, fighter)))
+        .collect();
+    for (ent, .., fighter) in &beez {
+        if fighter.is_some() && in_fight(ent, fighter).is_none() {
+            commands.entity(ent).remove::<BeeFighter>();
+        }
+    }
+
+    // Bees already fighting together start in the same group
+    let mut groups: Vec<usize> = (0..bees.len()).collect();
+    let mut by_fight: HashMap<Entity, usize> = HashMap::default();
+    for (i, (.., fight)) in bees.iter().enumerate() {
+        if let Some(fight) = fight {
+            let first = *by_fight.entry(*fight).or_insert(i);
+            groups[i] = root(&mut groups, first);
+        }
+    }
+
+    // GET READY TO BRUMBLE!
+    let index: HashMap<Entity, usize> = bees.iter().enumerate().map(|(i, bee)| (bee.0, i)).collect();
+    let mut new_links = vec![];
+    for i in 0..bees.len() {
+        let (_, faction_a, pos_a, fight_a) = bees[i];
+        for (other, _) in grid.within(pos_a, settings.fight_radius) {
+            // Inactive bees and larvae aren't indexed
+            let Some(&j) = index.get(&other).filter(|j| **j > i) else { continue };
+            let (_, faction_b, _, fight_b) = bees[j];
+            if (fight_a.is_some() && fight_a == fight_b)
+                || !diplomacy.will_fight(faction_a, faction_b)
+            {
+                continue;
+            }
+            let (a, b) = (root(&mut groups, i), root(&mut groups, j));
+            groups[a] = b;
+            new_links.push(i);
+        }
+    }
+    let mut changed: Vec<usize> = new_links.iter().map(|i| root(&mut groups, *i)).collect();
+    changed.sort_unstable();
+    changed.dedup();
+
+    for group in changed {
+        let members: Vec<usize> = (0..bees.len())
+            .filter(|i| root(&mut groups, *i) == group)
+            .collect();
+        let mut existing: Vec<Entity> = members.iter().filter_map(|i| bees[*i].3).collect();
+        existing.sort_unstable();
+        existing.dedup();
+        let member_ents: Vec<Entity> = members.iter().map(|i| bees[*i].0).collect();
+
+        // Join the first fight. Any others are emptied, and tidied up by bee_fight.
+        let fight = match existing.split_first() {
+            Some((keep, merged)) => {
+                let mut dealt = vec![];
+                for ent in merged {
+                    if let Ok(mut merged) = fights.get_mut(*ent) {
+                        merged.members.clear();
+                        dealt.extend(std::mem::take(&mut merged.dealt));
+                    }
+                }
+                if let Ok(mut fight) = fights.get_mut(*keep) {
+                    fight.members = member_ents.clone();
+                    for (hit, damage) in dealt {
+                        *fight.dealt.entry(hit).or_default() += damage;
+                    }
+                }
+                *keep
+            }
+            None => {
+                let center = members.iter().map(|i| bees[*i].2).sum::<Vec2>() / members.len() as f32;
+                commands.spawn((
+                    SpriteBundle {
+                        texture: assets.dust.clone(),
+                        transform: Transform::from_xyz(center.x, center.y, Layers::MIDGROUND + 100.0),
+                        ..default()
+                    },
+                    SimPosition::new(center),
+                    BeeFight {
+                        members: member_ents.clone(),
+                        timer: Timer::from_seconds(settings.fight_duration_secs, TimerMode::Once),
+                        punch_timer: Timer::from_seconds(settings.punch_interval_secs, TimerMode::Repeating),
+                        dealt: HashMap::default(),
+                    },
+                    OnGameScreen,
+                )).id()
+            }
+        };
+        for ent in member_ents {
+            commands.entity(ent).insert(BeeFighter { fight });
+        }
+    }
+}
+
+pub fn became_a_fighter(
+    mut commands: Commands,
+    beez: Query<Entity, Added<BeeFighter>>,
+){
+    // Bee just got in a fight.
+    for ent in beez.iter() {
+        commands
+            .entity(ent)
+            .remove::<Pathfinding>();
+    }
+}
+
+/// Trade punches. Every bee swings at the bees it's hostile to, splitting
+/// its damage between them. Faster bees land more punches, and brawny,
+/// healthy bees hit harder. A beaten bee might retreat instead of fighting
+/// to the death. A kill goes to the bee that did it the most damage.
+pub fn bee_fight(
+    mut commands: Commands,
+    mut bee_fight: Query<(Entity, &mut BeeFight, &mut SimPosition, &mut Transform)>,
+    mut bees: Query<(&RumbleBee, &mut Health, &Speed, &SimPosition, &Children, &BeeFighter, &BeeStats), Without<BeeFight>>,
+    mut arms: Query<(Entity, &mut AnimationIndices, &mut TextureAtlasSprite), With<ArmAnim>>,
+    mut records: Query<&mut BeeRecord>,
+    diplomacy: Res<Diplomacy>,
+    fixed_time: Res<FixedTime>,
+    settings: Res<GameSettings>,
+){
+    let mut rng = rand::thread_rng();
+    for (fight_ent, mut brawl, mut sim, mut transform) in bee_fight.iter_mut() {
+        // Drop anyone who died of old age or was moved to another fight
+        brawl.members.retain(|bee| bees.get(*bee).is_ok_and(|(.., f, _)| f.fight == fight_ent));
+
+        let fighters: Vec<(Entity, Faction, f32, f32, Vec2)> = brawl.members
+            .iter()
+            .filter_map(|ent| bees.get(*ent).ok().map(|(bee, health, speed, pos, ..)| {
+                (*ent, bee.faction, health.0, speed.speed, pos.pos)
+            }))
+            .collect();
+        let enemies_of = |faction: Faction| -> Vec<usize> {
+            (0..fighters.len())
+                .filter(|i| diplomacy.will_fight(faction, fighters[*i].1))
+                .collect()
+        };
+
+        if !fighters.iter().any(|f| !enemies_of(f.1).is_empty()) {
+            // Nobody left to fight
+            for (ent, ..) in &fighters {
+                commands.entity(*ent).remove::<BeeFighter>();
+            }
+            brawl.members.clear();
+            commands.entity(fight_ent).despawn();
+            continue;
+        }
+
+        // Dust cloud follows the brawl, bigger with more bees
+        sim.pos = fighters.iter().map(|f| f.4).sum::<Vec2>() / fighters.len() as f32;
+        transform.scale = Vec3::splat(0.6 + 0.15 * fighters.len() as f32);
+        transform.rotate_z(rng.gen_range(-0.3..0.3));
+
+        let timed_out = brawl.timer.tick(fixed_time.period).finished();
+        if timed_out {
+            // Out of puff: the weakest bee backs off, the rest sort it out later
+            if let Some((weakest, ..)) = fighters.iter().min_by(|a, b| a.2.total_cmp(&b.2)) {
+                commands.entity(*weakest).insert(Inactive {
+                    timer: Timer::from_seconds(settings.retreat_secs, TimerMode::Once)
+                });
+            }
+            for (ent, ..) in &fighters {
+                commands.entity(*ent).remove::<BeeFighter>();
+            }
+            brawl.members.clear();
+            commands.entity(fight_ent).despawn();
+            continue;
+        }
+        if !brawl.punch_timer.tick(fixed_time.period).just_finished() {
+            continue;
+        }
+
+        let mut damage = vec![0.0; fighters.len()];
+        for (attacker, faction, health, speed, _) in &fighters {
+            let enemies = enemies_of(*faction);
+            if enemies.is_empty() {
+                continue;
+            }
+            let enemy_speed = enemies.iter().map(|i| fighters[*i].3).sum::<f32>() / enemies.len() as f32;
+            if !rng.gen_bool(f64::from((speed / (speed + enemy_speed).max(f32::EPSILON)).clamp(0.0, 1.0))) {
+                continue;
+            }
+            let Ok((.., stats)) = bees.get(*attacker) else { continue };
+            let punch = settings.punch_damage
+                * stats.strength
+                * (0.5 + 0.5 * (health / stats.max_health).clamp(0.0, 1.0));
+            for i in &enemies {
+                let hit = punch / enemies.len() as f32;
+                damage[*i] += hit;
+                *brawl.dealt.entry((fighters[*i].0, *attacker)).or_default() += hit;
+            }
+            punch_arm(&mut commands, *attacker, &bees, &mut arms, &settings);
+        }
+
+        for ((ent, ..), hit) in fighters.iter().zip(damage) {
+            let Ok((_, mut health, ..)) = bees.get_mut(*ent) else { continue };
+            health.0 -= hit;
+            let beaten = if health.0 <= 0.0 {
+                commands.entity(*ent).insert(BeeKilled);
+                // The kill goes to whoever did the most damage
+                let killer = brawl.dealt
+                    .iter()
+                    .filter(|((target, _), _)| target == ent)
+                    .max_by(|a, b| a.1.total_cmp(b.1).then(a.0.1.cmp(&b.0.1)))
+                    .map(|((_, attacker), _)| *attacker);
+                if let Some(mut record) = killer.and_then(|k| records.get_mut(k).ok()) {
+                    record.kills += 1;
+                }
+                true
+            } else if hit > 0.0 && health.0 < settings.retreat_health {
+                // On easier difficulties a beaten bee is more likely to get away
+                let retreat = !rng.gen_bool(f64::from(settings.fight_on_chance));
+                if retreat {
+                    commands.entity(*ent).insert(Inactive {
+                        timer: Timer::from_seconds(settings.retreat_secs, TimerMode::Once)
+                    });
+                }
+                retreat
+            } else {
+                false
+            };
+            if beaten {
+                commands.entity(*ent).remove::<BeeFighter>();
+                brawl.members.retain(|bee| bee != ent);
+            }
+        }
+    }
+}
+
+fn punch_arm(
+    commands: &mut Commands,
+    bee: Entity,
+    bees: &Query<(&RumbleBee, &mut Health, &Speed, &SimPosition, &Children, &BeeFighter, &BeeStats), Without<BeeFight>>,
+    arms: &mut Query<(Entity, &mut AnimationIndices, &mut TextureAtlasSprite), With<ArmAnim>>,
+    settings: &GameSettings,
+) {
+    let Ok((.., kids, _, _)) = bees.get(bee) else { return };
+    for &child in kids.iter() {
+        if let Ok((arm, mut anim, mut sprite)) = arms.get_mut(child) {
+            anim.frames = vec![1];
+            anim.cur = 0;
+            sprite.index = 1;
+            commands.entity(arm).insert(Punching(
+                Timer::from_seconds(settings.punch_interval_secs * 0.5, TimerMode::Once)
+            ));
+        }
+    }
+}
+
+pub fn pull_back_arms(
+    mut commands: Commands,
+    mut arms: Query<(Entity, &mut Punching, &mut AnimationIndices, &mut TextureAtlasSprite)>,
+    fixed_time: Res<FixedTime>,
+) {
+    for (ent, mut punching, mut anim, mut sprite) in &mut arms {
+        if punching.0.tick(fixed_time.period).finished() {
+            anim.frames = vec![0];
+            anim.cur = 0;
+            sprite.index = 0;
+            commands.entity(ent).remove::<Punching>();
+        }
+    }
+}
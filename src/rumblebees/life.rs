@@ -0,0 +1,133 @@
+//! Life stages. Bees hatch as larvae, too small to fight, and grow into
+//! adults. Once their health runs low they're slow, bearded elders, but an
+//! elder that gets their health back is an adult again.
+//!
+//! There's no separate art per stage: each is the same part set with a
+//! difference. Larvae are smaller and have no wings, elders have a beard.
+use crate::game::{Health, OnGameScreen, Speed};
+use crate::organism::genome::Genome;
+use crate::prelude::*;
+use crate::rumblebees::looks::{self, Wings};
+use crate::rumblebees::{BEE_SCALE, BeeKilled, BeeRecord, BeeStats, OldTimer, RumbleBee};
+use crate::settings::GameSettings;
+use crate::simulation::SimPosition;
+use crate::{AssetCol, Layers};
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LifeStage {
+    Larva,
+    Adult,
+    Elder,
+}
+impl LifeStage {
+    #[must_use]
+    pub fn can_fight(self) -> bool {
+        self != Self::Larva
+    }
+    #[must_use]
+    pub fn speed_scale(self, settings: &GameSettings) -> f32 {
+        match self {
+            Self::Larva => settings.larva_speed_scale,
+            Self::Adult => 1.0,
+            Self::Elder => settings.elder_speed_scale,
+        }
+    }
+    /// Sprite scale, on top of `BEE_SCALE`.
+    #[must_use]
+    pub fn size(self) -> f32 {
+        match self {
+            Self::Larva => 0.6,
+            Self::Adult | Self::Elder => 1.0,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Beard;
+
+/// A puff of dust that fades away.
+#[derive(Component)]
+pub struct Poof(Timer);
+
+pub fn grow_up(
+    mut commands: Commands,
+    mut beez: Query<
+        (
+            Entity, &mut LifeStage, &BeeRecord, &Health, &BeeStats, &Genome,
+            &mut Speed, &mut Transform, &SimPosition, &Children,
+        ),
+        (With<RumbleBee>, Without<BeeKilled>),
+    >,
+    beards: Query<(), With<Beard>>,
+    mut wings: Query<&mut Visibility, With<Wings>>,
+    assets: Res<AssetCol>,
+    settings: Res<GameSettings>,
+) {
+    for (ent, mut stage, record, health, stats, genome, mut speed, mut transform, sim, kids)
+        in &mut beez
+    {
+        let next = match *stage {
+            LifeStage::Larva if record.age >= settings.larva_secs => LifeStage::Adult,
+            LifeStage::Adult if health.0 < settings.beard_health => LifeStage::Elder,
+            // Rejuvenated! Well clear of beard_health, so a bee on the edge doesn't flicker
+            LifeStage::Elder if health.0 > settings.beard_health + settings.beard_margin => {
+                LifeStage::Adult
+            }
+            current => current,
+        };
+        if next == *stage {
+            continue;
+        }
+
+        if *stage == LifeStage::Larva {
+            // Get wings
+            let mut iter = wings.iter_many_mut(kids.iter());
+            while let Some(mut visibility) = iter.fetch_next() {
+                *visibility = Visibility::Inherited;
+            }
+        }
+        if next == LifeStage::Elder {
+            // Get a beard
+            let beard = commands.spawn((looks::beard(&assets, genome), Beard)).id();
+            commands.entity(ent).insert(OldTimer).push_children(&[beard]);
+        } else if *stage == LifeStage::Elder {
+            // Lose a beard!
+            commands.entity(ent).remove::<OldTimer>();
+            for &kid in kids.iter().filter(|kid| beards.contains(**kid)) {
+                commands.entity(kid).despawn_recursive();
+            }
+        }
+
+        *stage = next;
+        speed.speed = stats.speed * next.speed_scale(&settings);
+        // Keep facing the same way (update_sprite)
+        let size = BEE_SCALE * next.size();
+        transform.scale = Vec3::new(size * transform.scale.x.signum(), size, 1.0);
+        commands.spawn((
+            SpriteBundle {
+                texture: assets.dust.clone(),
+                transform: Transform::from_xyz(sim.pos.x, sim.pos.y, Layers::MIDGROUND + 100.0)
+                    .with_scale(Vec3::splat(0.5)),
+                ..default()
+            },
+            Poof(Timer::from_seconds(0.4, TimerMode::Once)),
+            OnGameScreen,
+        ));
+    }
+}
+
+pub fn fade_poofs(
+    mut commands: Commands,
+    mut poofs: Query<(Entity, &mut Poof, &mut Sprite, &mut Transform)>,
+    fixed_time: Res<FixedTime>,
+) {
+    for (ent, mut poof, mut sprite, mut transform) in &mut poofs {
+        poof.0.tick(fixed_time.period);
+        let left = poof.0.percent_left();
+        sprite.color.set_a(left);
+        transform.scale = Vec3::splat(0.5 + (1.0 - left) * 0.5);
+        if poof.0.finished() {
+            commands.entity(ent).despawn();
+        }
+    }
+}
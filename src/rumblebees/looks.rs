@@ -0,0 +1,108 @@
+//! What a bee looks like. Bees are put together from part sprites, picked
+//! and sized by their genes, so related bees look alike. The face shows how
+//! the bee is getting on. Larvae hatch without wings, which show once they
+//! grow up (life.rs).
+use crate::game::{AnimationTimer, Health, ImageFrames};
+use crate::organism::Torso;
+use crate::organism::gene::{Chonk, Fluff, Shade, Stat, Wingspan};
+use crate::organism::genome::Genome;
+use crate::prelude::*;
+use crate::rumblebees::RumbleBee;
+use crate::rumblebees::brawl::BeeFighter;
+use crate::settings::GameSettings;
+use crate::terrain::Faction;
+use crate::AssetCol;
+
+/// Expression, in the order of `AssetCol::faces`.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Face {
+    Happy,
+    Angry, // Fighting
+    Strained, // Hurt or old
+}
+
+#[derive(Component)]
+pub struct Wings;
+
+/// Torso, wings and face for a new bee, to add as its children. The wings
+/// start hidden, as bees hatch as larvae.
+pub fn spawn_parts(
+    commands: &mut Commands,
+    assets: &AssetCol,
+    faction: Faction,
+    genome: &Genome,
+) -> [Entity; 3] {
+    // Chonky bees are wider more than taller
+    let chonk = genome.express::<Chonk>();
+    let shade = genome.express::<Shade>().value();
+    let torso = commands.spawn((
+        SpriteBundle {
+            texture: assets.torsos[faction.bee_sprite()].clone(),
+            sprite: Sprite { color: Color::rgb(shade, shade, shade), ..default() },
+            transform: Transform::from_scale(Vec3::new(chonk.scale(0.15), chonk.scale(0.08), 1.0)),
+            ..default()
+        },
+        Torso,
+    )).id();
+
+    let [up, mid, down] = [0, 1, 2].map(|i| assets.wings[i].clone());
+    let wingspan = genome.express::<Wingspan>().value();
+    let wings = commands.spawn((
+        SpriteBundle {
+            texture: up.clone(),
+            transform: Transform::from_xyz(0., 2., 0.01).with_scale(Vec3::splat(wingspan)),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        ImageFrames { frames: vec![up, mid.clone(), down, mid], cur: 0 },
+        AnimationTimer(Timer::from_seconds(0.04, TimerMode::Repeating)),
+        Wings,
+    )).id();
+
+    let face = commands.spawn((
+        SpriteBundle {
+            texture: assets.faces[Face::Happy as usize].clone(),
+            transform: Transform::from_xyz(0., 0., 0.02),
+            ..default()
+        },
+        Face::Happy,
+    )).id();
+
+    [torso, wings, face]
+}
+
+/// An old bee's beard, mid or long by their genes.
+#[must_use]
+pub fn beard(assets: &AssetCol, genome: &Genome) -> SpriteBundle {
+    let long = genome.express::<Fluff>().level() >= 0.5;
+    SpriteBundle {
+        texture: assets.beards[usize::from(long)].clone(),
+        transform: Transform::from_xyz(0., 0., 0.03),
+        ..default()
+    }
+}
+
+pub fn update_faces(
+    beez: Query<(&Health, Option<&BeeFighter>, &Children), With<RumbleBee>>,
+    mut faces: Query<(&mut Face, &mut Handle<Image>)>,
+    assets: Res<AssetCol>,
+    settings: Res<GameSettings>,
+) {
+    for (health, fighter, kids) in &beez {
+        let mood = if fighter.is_some() {
+            Face::Angry
+        } else if health.0 < settings.retreat_health {
+            Face::Strained
+        } else {
+            Face::Happy
+        };
+        for &kid in kids.iter() {
+            if let Ok((mut face, mut image)) = faces.get_mut(kid) {
+                if *face != mood {
+                    *face = mood;
+                    *image = assets.faces[mood as usize].clone();
+                }
+            }
+        }
+    }
+}
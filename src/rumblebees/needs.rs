@@ -0,0 +1,109 @@
+//! Hunger. Bees get hungry over time and feed by flying through leaves or
+//! past a plant's stalk, which also heals them a little. A starving bee
+//! loses health, so the shape of the garden matters.
+use bevy_ecs_tilemap::helpers::square_grid::neighbors::Neighbors;
+
+use crate::game::Health;
+use crate::prelude::*;
+use crate::rumblebees::{BeeKilled, BeeStats, RumbleBee};
+use crate::settings::GameSettings;
+use crate::simulation::{SimPosition, sim_delta};
+use crate::terrain::{GAP_LEFT, Tile, px_to_tilepos};
+
+/// 0 is full, 100 is starving.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct Hunger(pub f32);
+impl Hunger {
+    pub const STARVING: f32 = 100.0;
+
+    #[must_use]
+    pub fn is_hungry(self, settings: &GameSettings) -> bool {
+        self.0 >= settings.hungry_at
+    }
+}
+
+fn is_food(tile: Option<&Tile>) -> bool {
+    matches!(tile, Some(Tile::Stalk { .. } | Tile::Leaves { .. }))
+}
+
+/// A bee at `pos` can eat: it's in leaves or beside a plant.
+#[must_use]
+pub fn can_eat(
+    pos: TilePos,
+    storage: &TileStorage,
+    map_size: &TilemapSize,
+    tiles: &Query<&Tile>,
+) -> bool {
+    std::iter::once(pos)
+        .chain(Neighbors::get_square_neighboring_positions(&pos, map_size, true).iter().copied())
+        .any(|pos| is_food(storage.get(&pos).and_then(|ent| tiles.get(ent).ok())))
+}
+
+/// Where bees can go to eat: leaves, and open tiles beside a stalk.
+#[must_use]
+pub fn food_tiles(
+    storage: &TileStorage,
+    map_size: &TilemapSize,
+    tiles: &Query<&Tile>,
+) -> Vec<TilePos> {
+    let tile_at = |pos: &TilePos| storage.get(pos).and_then(|ent| tiles.get(ent).ok());
+    let mut food = vec![];
+    for x in 0..map_size.x {
+        for y in 0..map_size.y {
+            let pos = TilePos { x, y };
+            let fits = match tile_at(&pos) {
+                Some(Tile::Leaves { .. }) => true,
+                Some(Tile::Air) => Neighbors::get_square_neighboring_positions(&pos, map_size, false)
+                    .iter()
+                    .any(|n| matches!(tile_at(n), Some(Tile::Stalk { .. }))),
+                _ => false,
+            };
+            if fits {
+                food.push(pos);
+            }
+        }
+    }
+    food
+}
+
+pub fn get_hungry(
+    mut commands: Commands,
+    mut beez: Query<(Entity, &mut Hunger, &mut Health), (With<RumbleBee>, Without<BeeKilled>)>,
+    fixed_time: Res<FixedTime>,
+    settings: Res<GameSettings>,
+) {
+    let dt = sim_delta(&fixed_time);
+    for (ent, mut hunger, mut health) in &mut beez {
+        hunger.0 = (hunger.0 + settings.hunger_per_sec * dt).min(Hunger::STARVING);
+        if hunger.0 >= Hunger::STARVING {
+            health.0 -= settings.starving_damage_per_sec * dt;
+            if health.0 <= 0.0 {
+                commands.entity(ent).insert(BeeKilled);
+            }
+        }
+    }
+}
+
+pub fn feed(
+    mut beez: Query<
+        (&mut Hunger, &mut Health, &BeeStats, &SimPosition),
+        (With<RumbleBee>, Without<BeeKilled>),
+    >,
+    tiles: Query<&Tile>,
+    tilemap: Query<(&TileStorage, &TilemapSize, &TilemapGridSize)>,
+    fixed_time: Res<FixedTime>,
+    settings: Res<GameSettings>,
+) {
+    let (storage, map_size, grid_size) = tilemap.single();
+    let dt = sim_delta(&fixed_time);
+    for (mut hunger, mut health, stats, sim) in &mut beez {
+        if hunger.0 <= 0.0 {
+            continue;
+        }
+        let pos = px_to_tilepos(sim.pos - Vec2::new(GAP_LEFT, 0.0), grid_size);
+        if can_eat(pos, storage, map_size, &tiles) {
+            hunger.0 = (hunger.0 - settings.eat_per_sec * dt).max(0.0);
+            health.0 = (health.0 + settings.eat_heal_per_sec * dt).min(stats.max_health);
+        }
+    }
+}
@@ -0,0 +1,96 @@
+//! Bees bucketed by the tile they're in, rebuilt every simulation step, so
+//! collision checks only look at bees in nearby tiles.
+use bevy::utils::HashMap;
+
+use crate::prelude::*;
+use crate::rumblebees::RumbleBee;
+use crate::simulation::SimPosition;
+use crate::terrain::{GAP_LEFT, px_to_tilepos};
+
+#[derive(Resource, Debug, Default)]
+pub struct BeeGrid {
+    cells: HashMap<TilePos, Vec<(Entity, Vec2)>>,
+    grid_size: TilemapGridSize,
+}
+impl BeeGrid {
+    fn cell(&self, pos: Vec2) -> TilePos {
+        px_to_tilepos(pos - Vec2::new(GAP_LEFT, 0.0), &self.grid_size)
+    }
+
+    /// Bees within `radius` of `pos`.
+    pub fn within(&self, pos: Vec2, radius: f32) -> impl Iterator<Item = (Entity, Vec2)> + '_ {
+        let min = self.cell(pos - Vec2::splat(radius));
+        let max = self.cell(pos + Vec2::splat(radius));
+        (min.y..=max.y)
+            .flat_map(move |y| (min.x..=max.x).map(move |x| TilePos { x, y }))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+            .filter(move |(_, bee_pos)| bee_pos.distance(pos) < radius)
+    }
+
+    fn insert(&mut self, ent: Entity, pos: Vec2) {
+        let cell = self.cell(pos);
+        self.cells.entry(cell).or_default().push((ent, pos));
+    }
+}
+
+pub fn build_bee_grid(
+    mut grid: ResMut<BeeGrid>,
+    beez: Query<(Entity, &SimPosition), With<RumbleBee>>,
+    tilemap: Query<&TilemapGridSize>,
+) {
+    let grid_size = tilemap.single();
+    grid.grid_size = *grid_size;
+    grid.cells.clear();
+    for (ent, sim) in &beez {
+        grid.insert(ent, sim.pos);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn test_within_matches_brute_force() {
+        let size = 40.0;
+        let mut grid = BeeGrid {
+            grid_size: TilemapGridSize { x: size, y: size },
+            ..default()
+        };
+        let mut bees = vec![];
+        // Cell corners and edges, off the map on both sides, and a scatter
+        for i in -3..12 {
+            let edge = GAP_LEFT + i as f32 * size;
+            bees.push(Vec2::new(edge, i as f32 * size));
+            bees.push(Vec2::new(edge - 0.01, edge + 0.01));
+        }
+        bees.extend([Vec2::new(-500.0, -500.0), Vec2::new(-1.0, 20.0), Vec2::new(5000.0, 3.0)]);
+        let mut rng = StdRng::seed_from_u64(41);
+        bees.extend(
+            (0..300).map(|_| Vec2::new(rng.gen_range(-200.0..900.0), rng.gen_range(-200.0..700.0)))
+        );
+        for (i, pos) in bees.iter().enumerate() {
+            grid.insert(Entity::from_raw(i as u32), *pos);
+        }
+
+        let mut queries: Vec<Vec2> = bees.iter().step_by(7).copied().collect();
+        queries.extend([Vec2::new(GAP_LEFT, 0.0), Vec2::new(-60.0, -60.0), Vec2::new(5000.0, 0.0)]);
+        // Smaller than, exactly and bigger than a cell
+        for radius in [5.0, size, 50.0, 130.0] {
+            for pos in &queries {
+                let mut found: Vec<u32> =
+                    grid.within(*pos, radius).map(|(ent, _)| ent.index()).collect();
+                found.sort_unstable();
+                let expected: Vec<u32> = (0..bees.len() as u32)
+                    .filter(|i| bees[*i as usize].distance(*pos) < radius)
+                    .collect();
+                assert_eq!(found, expected, "within({pos}, {radius})");
+            }
+        }
+    }
+}
@@ -1,12 +1,233 @@
-pub const RUMBLEBEE_SPEED_START: f32 = 50.0;
-pub const RUMBLEBEE_SPEED_MAX: f32 = 120.0;
-pub const RUMBLEBEE_PER_EGG_SPEEDUP_PERC: f32 = 0.015;
-pub const RUMBLEBEE_SPEED_VARIANCE: f32 = 0.2; // 0..1 = 0..100% variance per bee
-pub const AGING_AMOUNT_PER_TICK: f32 = 1.5;
-
-pub const DIG_REPEAT_IN_SECS: f32 = 0.5; // how quickly to dig
-pub const DIG_POWER: u8 = 25; // each dig HP amount
-
-pub const EGG_SPAWN_TIME_START: f32 = 6.0; // How often to spawn an egg.
-pub const EGG_SPAWN_TIME_END: f32 = 2.0; // As game progresses, eggs spawn faster
-pub const EGG_SPAWN_SPEEDUP_PERC: f32 = 0.01; // How fast to get faster
+//! Game tuning values, loaded from `assets/settings.ron`.
+//!
+//! Editing the file while the game runs hot-reloads it (native only).
+//! The `GameSettings` resource holds the loaded values with the current
+//! [`Difficulty`] applied.
+use bevy::asset::{AssetLoader, BoxedFuture, LoadContext, LoadedAsset};
+use bevy::reflect::{TypePath, TypeUuid};
+use serde::Deserialize;
+
+use crate::difficulty::{Difficulty, DifficultyProfile};
+use crate::level::LevelRules;
+use crate::prelude::*;
+
+#[derive(Resource, Deserialize, TypeUuid, TypePath, Debug, Clone)]
+#[uuid = "6b1f4d0e-3c1a-4a8e-9d62-2f5b7c9e8a41"]
+#[serde(default)]
+pub struct GameSettings {
+    pub rumblebee_speed_start: f32,
+    pub rumblebee_speed_max: f32,
+    pub rumblebee_per_egg_speedup_perc: f32,
+    pub rumblebee_speed_variance: f32, // 0..1 = 0..100% variance from the speed gene
+    pub mutation_rate: f32, // 0..1 chance each base of a newborn's DNA changes
+    pub mate_radius: f32, // A bee this close to the collector is the second parent
+    pub gene_seed: Option<u64>, // Same seed breeds and names the same bees
+    pub aging_amount_per_tick: f32,
+    pub beard_health: f32, // Bees below this health are elders, with a beard
+    pub beard_margin: f32, // How far above beard_health an elder heals to lose it
+    pub larva_secs: f32, // How long a hatchling takes to grow up
+    pub larva_speed_scale: f32,
+    pub elder_speed_scale: f32,
+    pub hunger_per_sec: f32, // Hunger goes from 0 (full) to 100 (starving)
+    pub hungry_at: f32, // Bees this hungry look for food before eggs
+    pub starving_damage_per_sec: f32,
+    pub eat_per_sec: f32, // Hunger lost while in leaves or beside a stalk
+    pub eat_heal_per_sec: f32,
+    pub rest_secs: f32, // How long a tired bee stays put
+    pub rest_heal_per_sec: f32,
+
+    pub fight_duration_secs: f32, // Longest a fight goes before the weaker bee retreats
+    pub punch_interval_secs: f32,
+    pub punch_damage: f32, // At full health. Weaker bees hit softer
+    pub retreat_health: f32, // Below this a beaten bee might retreat
+    pub retreat_secs: f32,
+    pub fight_radius: f32, // How close enemy bees get before they fight
+    pub sight_radius: f32, // How far away bees notice enemies, to pick a fight or flee
+    #[serde(skip)]
+    pub fight_on_chance: f32, // Set from the difficulty profile
+    pub egg_collect_radius: f32,
+
+    pub dig_repeat_in_secs: f32, // how quickly to dig
+    pub dig_power: u8, // each dig HP amount
+
+    pub egg_spawn_time_start: f32, // How often to spawn an egg.
+    pub egg_spawn_time_end: f32, // As game progresses, eggs spawn faster
+    pub egg_spawn_speedup_perc: f32, // How fast to get faster
+    pub egg_spawn_jitter: f32, // 0..1 variance for the Random spawn mode
+
+    pub plant_grow_secs: f32, // Time to grow each tile of stalk
+    pub plant_fruit_secs: f32, // How long an egg waits before it rots
+    pub plant_decay_secs: f32, // Time for each tile of a dead plant to decay
+    pub leaves_speed_scale: f32, // Bee speed when flying through leaves
+
+    pub poo_chance_per_sec: f32, // Chance each bee drops some poo
+    pub poo_fall_speed: f32,
+    pub poo_rot_secs: f32, // Bee poo that isn't used up crumbles away after this long
+    pub fertilizer_spawn_weight: f32, // How much likelier fertilized soil gets a plant
+    pub fertilizer_grow_scale: f32, // < 1 means fertilized plants grow faster
+    pub fertilizer_faction_bias: f32, // 0..1 chance the egg matches the poo
+
+    pub soil_fertility_drain: f32, // 0..1 fertility a plant uses up
+    pub soil_regen_per_sec: f32,
+    pub fertilizer_regen_per_sec: f32, // Extra regrowth beside fertilizer
+
+    pub tile_fall_secs: f32, // Falling tiles move a tile this often
+    pub falling_tile_damage: f32, // Health a bee loses when a tile lands on it
+
+    #[serde(skip)]
+    pub starting_dirt: Option<u32>, // Set from the difficulty profile
+
+    pub level: LevelRules,
+
+    pub easy: DifficultyProfile,
+    pub normal: DifficultyProfile,
+    pub hard: DifficultyProfile,
+}
+
+impl Default for GameSettings {
+    fn default() -> Self {
+        Self {
+            rumblebee_speed_start: 50.0,
+            rumblebee_speed_max: 120.0,
+            rumblebee_per_egg_speedup_perc: 0.015,
+            rumblebee_speed_variance: 0.2,
+            mutation_rate: 0.02,
+            mate_radius: 80.0,
+            gene_seed: None,
+            aging_amount_per_tick: 1.5,
+            beard_health: 20.0,
+            beard_margin: 10.0,
+            larva_secs: 6.0,
+            larva_speed_scale: 0.6,
+            elder_speed_scale: 0.7,
+            hunger_per_sec: 2.0,
+            hungry_at: 50.0,
+            starving_damage_per_sec: 3.0,
+            eat_per_sec: 25.0,
+            eat_heal_per_sec: 4.0,
+            rest_secs: 3.0,
+            rest_heal_per_sec: 1.0,
+
+            fight_duration_secs: 8.0,
+            punch_interval_secs: 0.4,
+            punch_damage: 12.0,
+            retreat_health: 35.0,
+            retreat_secs: 2.0,
+            fight_radius: 50.0,
+            sight_radius: 200.0,
+            fight_on_chance: 1.0,
+            egg_collect_radius: 20.0,
+
+            dig_repeat_in_secs: 0.5,
+            dig_power: 25,
+
+            egg_spawn_time_start: 6.0,
+            egg_spawn_time_end: 2.0,
+            egg_spawn_speedup_perc: 0.01,
+            egg_spawn_jitter: 0.5,
+
+            plant_grow_secs: 0.75,
+            plant_fruit_secs: 25.0,
+            plant_decay_secs: 1.5,
+            leaves_speed_scale: 0.35,
+
+            poo_chance_per_sec: 0.03,
+            poo_fall_speed: 120.0,
+            poo_rot_secs: 40.0,
+            fertilizer_spawn_weight: 4.0,
+            fertilizer_grow_scale: 0.5,
+            fertilizer_faction_bias: 0.75,
+
+            soil_fertility_drain: 0.5,
+            soil_regen_per_sec: 0.01,
+            fertilizer_regen_per_sec: 0.05,
+
+            tile_fall_secs: 0.15,
+            falling_tile_damage: 25.0,
+
+            starting_dirt: None,
+
+            level: LevelRules::default(),
+
+            easy: DifficultyProfile {
+                egg_spawn_time_scale: 0.8,
+                aging_scale: 0.7,
+                speed_ramp_scale: 0.5,
+                fight_on_chance: 0.6,
+                ..default()
+            },
+            normal: DifficultyProfile::default(),
+            hard: DifficultyProfile {
+                egg_spawn_time_scale: 1.25,
+                egg_spawn_speedup_scale: 1.5,
+                aging_scale: 1.3,
+                speed_ramp_scale: 1.5,
+                starting_dirt: Some(40),
+                fight_on_chance: 1.0,
+            },
+        }
+    }
+}
+
+/// The settings as loaded from the file, before difficulty is applied.
+#[derive(Resource, Default, Deref)]
+struct BaseSettings(GameSettings);
+
+#[derive(Default)]
+pub struct GameSettingsLoader;
+impl AssetLoader for GameSettingsLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let settings = ron::de::from_bytes::<GameSettings>(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(settings));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ron"]
+    }
+}
+
+pub struct SettingsPlugin;
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_asset::<GameSettings>()
+            .init_asset_loader::<GameSettingsLoader>()
+            .init_resource::<BaseSettings>()
+            .init_resource::<GameSettings>()
+            .init_resource::<Difficulty>()
+            .add_systems(PreUpdate, apply_settings);
+    }
+}
+
+/// Update the resource whenever the settings asset (re)loads or the
+/// difficulty changes.
+fn apply_settings(
+    mut events: EventReader<AssetEvent<GameSettings>>,
+    assets: Res<Assets<GameSettings>>,
+    mut base: ResMut<BaseSettings>,
+    difficulty: Res<Difficulty>,
+    mut settings: ResMut<GameSettings>,
+) {
+    for ev in events.iter() {
+        match ev {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                if let Some(loaded) = assets.get(handle) {
+                    base.0 = loaded.clone();
+                    screen_print!(sec: 3.0, "Loaded game settings");
+                }
+            }
+            AssetEvent::Removed { .. } => (),
+        }
+    }
+
+    if base.is_changed() || difficulty.is_changed() {
+        *settings = base.with_difficulty(*difficulty);
+    }
+}
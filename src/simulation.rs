@@ -0,0 +1,74 @@
+//! Fixed timestep simulation.
+//!
+//! Gameplay systems run in `FixedUpdate` and read their delta from
+//! `FixedTime::period`, so behaviour doesn't depend on the frame rate.
+//! Entities with a [`SimPosition`] are drawn interpolated between the last
+//! two simulation steps.
+use crate::prelude::*;
+use crate::GameState;
+
+/// Simulation steps per second.
+pub const SIM_STEPS_PER_SEC: f32 = 60.0;
+
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SimSet {
+    /// Bookkeeping before the step: store previous positions, sync navmesh.
+    Prepare,
+    /// The gameplay systems.
+    Simulate,
+}
+
+/// Position of an entity in the simulation. The entity's `Transform` is
+/// only the rendered position, so don't read it for gameplay.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct SimPosition {
+    pub pos: Vec2,
+    pub prev: Vec2,
+}
+impl SimPosition {
+    #[must_use]
+    pub fn new(pos: Vec2) -> Self {
+        Self { pos, prev: pos }
+    }
+}
+
+pub struct SimulationPlugin;
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(FixedTime::new_from_secs(1.0 / SIM_STEPS_PER_SEC))
+            .configure_sets(FixedUpdate, (SimSet::Prepare, SimSet::Simulate).chain())
+            .configure_set(FixedUpdate, SimSet::Prepare.run_if(in_state(GameState::InGame)))
+            .configure_set(FixedUpdate, SimSet::Simulate.run_if(in_state(GameState::InGame)))
+            .add_systems(FixedUpdate, store_previous_positions.in_set(SimSet::Prepare))
+            .add_systems(
+                Update,
+                interpolate_positions.run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+/// The delta to use for one simulation step.
+#[must_use]
+pub fn sim_delta(fixed_time: &FixedTime) -> f32 {
+    fixed_time.period.as_secs_f32()
+}
+
+fn store_previous_positions(mut query: Query<&mut SimPosition>) {
+    for mut sim in &mut query {
+        sim.prev = sim.pos;
+    }
+}
+
+/// Place sprites between the previous and current simulation step.
+pub fn interpolate_positions(
+    fixed_time: Res<FixedTime>,
+    mut query: Query<(&SimPosition, &mut Transform)>,
+) {
+    let alpha = (fixed_time.accumulated().as_secs_f32() / sim_delta(&fixed_time)).min(1.0);
+    for (sim, mut transform) in &mut query {
+        let pos = sim.prev.lerp(sim.pos, alpha);
+        transform.translation.x = pos.x;
+        transform.translation.y = pos.y;
+    }
+}
@@ -3,6 +3,8 @@ use bevy::{prelude::*, input::ButtonState};
 use bevy::window::PrimaryWindow;
 
 use crate::AssetCol;
+use crate::difficulty::Difficulty;
+use crate::pointer::Pointer;
 use crate::{despawn_screen, GameState};
 
 pub struct SplashPlugin;
@@ -10,7 +12,11 @@ pub struct SplashPlugin;
 impl Plugin for SplashPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(OnEnter(GameState::Splash), splash_setup)
-            .add_systems(Update, (countdown).run_if(in_state(GameState::Splash)))
+            .add_systems(Update, (
+                choose_difficulty,
+                show_difficulty.after(choose_difficulty),
+                countdown.after(choose_difficulty),
+            ).run_if(in_state(GameState::Splash)))
             .add_systems(OnExit(GameState::Splash), despawn_screen::<OnSplashScreen>);
     }
 }
@@ -18,6 +24,13 @@ impl Plugin for SplashPlugin {
 #[derive(Component)]
 struct OnSplashScreen;
 
+#[derive(Component)]
+struct DifficultyText;
+
+/// Screen area of the difficulty text. Clicking it changes difficulty.
+const DIFFICULTY_TOP: f32 = 150.0;
+const DIFFICULTY_HEIGHT: f32 = 50.0;
+
 #[derive(Resource, Deref, DerefMut)]
 struct SplashTimer(Timer);
 
@@ -78,6 +91,60 @@ fn splash_setup(
                 ..default()
             }),
     ));
+
+    commands.spawn((
+        OnSplashScreen,
+        DifficultyText,
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: assets.font.clone(),
+                font_size: 40.0,
+                color: Color::WHITE,
+                ..default()
+            },
+        )
+            .with_text_alignment(TextAlignment::Center)
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                left: Val::Px(20.0),
+                top: Val::Px(DIFFICULTY_TOP),
+                ..default()
+            }),
+    ));
+}
+
+fn on_difficulty_text(pointer: &Pointer, window: &Window) -> bool {
+    // Pointer is in world space (origin bottom left), UI is from the top
+    let top = window.height() - pointer.pos.y;
+    (DIFFICULTY_TOP..DIFFICULTY_TOP + DIFFICULTY_HEIGHT).contains(&top)
+}
+
+fn choose_difficulty(
+    keys: Res<Input<KeyCode>>,
+    pointer: Res<Pointer>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut difficulty: ResMut<Difficulty>,
+) {
+    if keys.any_just_pressed([KeyCode::Left, KeyCode::Up]) {
+        *difficulty = difficulty.prev();
+    }
+    if keys.any_just_pressed([KeyCode::Right, KeyCode::Down]) {
+        *difficulty = difficulty.next();
+    }
+    let Ok(window) = window_query.get_single() else { return };
+    if pointer.released && on_difficulty_text(&pointer, window) {
+        *difficulty = difficulty.next();
+    }
+}
+
+fn show_difficulty(
+    difficulty: Res<Difficulty>,
+    mut text: Query<&mut Text, With<DifficultyText>>,
+) {
+    for mut text in &mut text {
+        text.sections[0].value = format!("< {} >", difficulty.label());
+    }
 }
 
 fn countdown(
@@ -85,10 +152,19 @@ fn countdown(
     mut key_evr: EventReader<KeyboardInput>,
     buttons: Res<Input<MouseButton>>,
     touches: Res<Touches>,
+    pointer: Res<Pointer>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
 ) {
     //if timer.tick(time.delta()).finished()
     let mut done = false;
     for ev in key_evr.iter() {
+        // Arrow keys choose the difficulty
+        if matches!(
+            ev.key_code,
+            Some(KeyCode::Left | KeyCode::Right | KeyCode::Up | KeyCode::Down)
+        ) {
+            continue;
+        }
         match ev.state {
             ButtonState ::Released => {
                 done = true;
@@ -96,7 +172,10 @@ fn countdown(
             _ => ()
         };
     }
-    for _ in buttons.get_just_released() {
+    let on_difficulty = window_query
+        .get_single()
+        .is_ok_and(|window| on_difficulty_text(&pointer, window));
+    if buttons.get_just_released().next().is_some() && !on_difficulty {
         done = true;
     }
     for finger in touches.iter() {
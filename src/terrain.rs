@@ -8,6 +8,7 @@ use bevy_ecs_tilemap::prelude::*;
 use bevy_kira_audio::prelude::*;
 use rand::Rng;
 use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
 
 use crate::AssetCol;
 use crate::GameState;
@@ -19,11 +20,21 @@ use crate::game::{OnGameScreen,GameData,HealthByte};
 use crate::pathfinding::Navmesh;
 use crate::inventory::Inventory;
 use crate::pointer::{Pointer, update_pointer};
-use crate::settings::{
-    EGG_SPAWN_TIME_START,
-    EGG_SPAWN_TIME_END,
-    EGG_SPAWN_SPEEDUP_PERC
-};
+use crate::simulation::SimSet;
+use crate::rumblebees::RumbleBee;
+use crate::settings::GameSettings;
+
+pub mod egg_spawn;
+pub mod falling;
+pub mod fertilizer;
+pub mod plant;
+pub mod soil;
+
+pub use egg_spawn::EggSpawnMode;
+use egg_spawn::{EggSpawnStrategy, SpawnContext};
+use falling::{Anchored, FallTimer};
+pub use plant::{Plant, PlantStatus};
+use soil::Fertility;
 
 pub const MAP_COLS: u32 = 23;
 pub const MAP_ROWS: u32 = 15;
@@ -32,20 +43,60 @@ pub const GAP_LEFT: f32 = TILE_SIZE * 2.0;
 pub const GAP_BOTTOM: f32 = TILE_SIZE * 0.0;
 
 // Some egg-spawing-order ideas to try
+#[derive(Deserialize, Debug, Clone, Default)]
 pub enum EggFactionMode {
+    #[default]
     Random,
-    FlipFlopNoGreen,
+    FlipFlopNoGreen, // Each faction in turn: Red, Blue, Red, Blue...
     RandomNoGreen,
-    Sequence,
-    PingPong
+    Sequence(Vec<Faction>), // Repeats the list
+    PingPong // Factions then Green, and back: Red, Blue, Green, Blue, Red...
 }
 
-// Some egg-spawning-timing
-pub enum EggSpawnMode {
-    Constant,
-    Random,
-    SpeedUp,
-    BeeBased
+/// Picks the faction of each new egg for an [`EggFactionMode`].
+#[derive(Resource, Debug, Default)]
+pub struct EggFactionPicker {
+    index: usize,
+    backwards: bool,
+}
+impl EggFactionPicker {
+    /// The next egg, for a level where `factions` are fighting.
+    pub fn next(&mut self, mode: &EggFactionMode, factions: &[Faction]) -> Faction {
+        let with_green: Vec<Faction> = factions
+            .iter()
+            .copied()
+            .chain(std::iter::once(Faction::Green))
+            .collect();
+        match mode {
+            EggFactionMode::Random => Faction::random(&with_green),
+            EggFactionMode::RandomNoGreen => Faction::random(factions),
+            EggFactionMode::FlipFlopNoGreen if factions.is_empty() => Faction::Green,
+            EggFactionMode::FlipFlopNoGreen => self.cycle(factions),
+            EggFactionMode::Sequence(sequence) if sequence.is_empty() => Faction::random(&with_green),
+            EggFactionMode::Sequence(sequence) => self.cycle(sequence),
+            EggFactionMode::PingPong => self.ping_pong(&with_green),
+        }
+    }
+    fn cycle(&mut self, factions: &[Faction]) -> Faction {
+        let faction = factions[self.index % factions.len()];
+        self.index = (self.index + 1) % factions.len();
+        faction
+    }
+    fn ping_pong(&mut self, factions: &[Faction]) -> Faction {
+        let last = factions.len() - 1;
+        let faction = factions[self.index.min(last)];
+        if self.index >= last {
+            self.backwards = true;
+        } else if self.index == 0 {
+            self.backwards = false;
+        }
+        self.index = if self.backwards {
+            self.index.min(last).saturating_sub(1)
+        } else {
+            self.index + 1
+        };
+        faction
+    }
 }
 
 #[derive(Component, Copy, Clone, Debug)]
@@ -98,9 +149,14 @@ impl Tile {
         match tile {
             Tile::Air => false,
             Tile::Egg { .. } => false,
+            Tile::Leaves { .. } => false, // Bees can squeeze through, slowly
             _ => true
         }
     }
+    /// Solid, if dirt could be dug through.
+    pub fn is_solid_for_digger(tile: Tile) -> bool {
+        !matches!(tile, Tile::Dirt { .. }) && Tile::is_solid(tile)
+    }
 }
 
 pub fn find_empty_tile(navmesh:&Navmesh, map_size:&TilemapSize) -> Option<TilePos> {
@@ -154,45 +210,89 @@ impl Plugin for TerrainPlugin {
             .add_systems(OnEnter(GameState::InGame), terrain_setup)
             .add_systems(First, update_pointer)
             .add_systems(Update, (
-                spawn_plant,
                 highlight_tile,
                 update_tile,
-                update_navmesh_on_tile_change.after(update_tile),
-                remove_conflicting_paths_on_tile_change.after(update_tile),
-            ).run_if(in_state(GameState::InGame)));
+                soil::show_fertility.after(update_tile),
+            ).run_if(in_state(GameState::InGame)))
+            .add_systems(FixedUpdate, (
+                update_navmesh_on_tile_change,
+                remove_conflicting_paths_on_tile_change,
+            ).in_set(SimSet::Prepare))
+            .add_systems(FixedUpdate, (
+                spawn_plant,
+                plant::grow_plants,
+                fertilizer::drop_poo,
+                fertilizer::settle_poo,
+                fertilizer::rot_poo,
+                falling::fall_loose_tiles,
+                soil::regenerate_soil,
+            ).in_set(SimSet::Simulate));
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Bee teams. Which ones fight is set by the level's `factions`. Green eggs
+/// are a wildcard that any bee can collect.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Faction {
     Red,
     Blue,
-    Green
+    Green,
+    Pink,
+    Purple,
+    Yellow,
 }
 impl Faction {
-    const FACTIONS: &'static [Self] = &[
-        Self::Red,
-        Self::Blue,
-        Self::Green,
-    ];
     #[must_use]
-    pub fn random() -> Self {
+    pub fn random(factions: &[Faction]) -> Self {
         let mut rng = rand::thread_rng();
-        *Self::FACTIONS.choose(&mut rng).unwrap()
+        *factions.choose(&mut rng).unwrap_or(&Self::Green)
+    }
+    /// Column of the egg and poo tiles in the tile atlas.
+    #[must_use]
+    pub fn egg_style(self) -> u8 {
+        match self {
+            Self::Pink => 0,
+            Self::Blue => 1,
+            Self::Purple => 2,
+            Self::Red => 3,
+            Self::Green => 4,
+            Self::Yellow => 5,
+        }
+    }
+    #[must_use]
+    pub fn from_egg_style(style: u8) -> Self {
+        match style {
+            0 => Self::Pink,
+            1 => Self::Blue,
+            2 => Self::Purple,
+            3 => Self::Red,
+            5 => Self::Yellow,
+            _ => Self::Green,
+        }
+    }
+    /// Bee body in the character atlas, and its torso in `AssetCol::torsos`.
+    #[must_use]
+    pub fn bee_sprite(self) -> usize {
+        match self {
+            Self::Blue => 0,
+            Self::Pink => 1,
+            Self::Purple => 2,
+            Self::Green => 3,
+            Self::Red => 4,
+            Self::Yellow => 5,
+        }
+    }
+    #[must_use]
+    pub fn color(self) -> Color {
+        match self {
+            Self::Red => Color::rgb(0.95, 0.2, 0.25),
+            Self::Blue => Color::rgb(0.35, 0.65, 0.95),
+            Self::Green => Color::rgb(0.35, 0.9, 0.35),
+            Self::Pink => Color::rgb(0.85, 0.35, 0.55),
+            Self::Purple => Color::rgb(0.65, 0.45, 0.95),
+            Self::Yellow => Color::rgb(0.95, 0.9, 0.3),
+        }
     }
-}
-
-#[derive(Debug)]
-enum PlantStatus {
-    Dead,
-    Growing,
-    Fruiting,
-}
-
-#[derive(Component, Debug)]
-pub struct Plant {
-    pub ptype: Faction,
-    status: PlantStatus,
 }
 
 #[derive(Component, Debug)]
@@ -223,12 +323,17 @@ pub struct Cursor;
 #[derive(Component)]
 pub struct Terrarium;
 
-#[derive(Resource, Deref, DerefMut)]
-struct PlantSpawner(Timer);
+#[derive(Resource)]
+struct PlantSpawner {
+    timer: Timer,
+    mode: EggSpawnMode, // What the strategy was made from
+    strategy: Box<dyn EggSpawnStrategy>,
+}
 
 fn terrain_setup(
     mut commands: Commands,
-    assets: Res<AssetServer>
+    assets: Res<AssetServer>,
+    settings: Res<GameSettings>
 ) {
     let texture = assets.load("img/tiles.png");
 
@@ -254,23 +359,48 @@ fn terrain_setup(
                 tilemap_entity,
             );
             tile_storage.set(&tile_pos, tile_entity);
-            navmesh.set_solid(tile_pos, match tile {
-                Tile::Air => false,
-                Tile::Egg { .. } => false,
-                _ => true
-            });
-            navmesh_no_dirt.set_solid(tile_pos, match tile {
-                Tile::Air => false,
-                Tile::Egg { .. } => false,
-                Tile::Dirt { .. } => false,
-                _ => true
-            });
+            if matches!(tile, Tile::Dirt { .. }) {
+                commands.entity(tile_entity).insert(Anchored);
+            }
+            navmesh.set_solid(tile_pos, Tile::is_solid(tile));
+            navmesh_no_dirt.set_solid(tile_pos, Tile::is_solid_for_digger(tile));
             tiles.push(tile_entity);
         }
     }
 
-    commands.insert_resource(PlantSpawner(
-        Timer::new(Duration ::from_secs_f32(EGG_SPAWN_TIME_START), TimerMode::Repeating),
+    // Plants already on the map: an egg on top of some stalks
+    for y in 0..map_size.y {
+        for x in 0..map_size.x {
+            let egg_pos = TilePos { x, y };
+            let Tile::Egg { style } = get_tile_from_ascii(egg_pos, map_size) else {
+                continue;
+            };
+            let mut plant_tiles = vec![];
+            let mut pos = egg_pos;
+            while pos.y > 0 && matches!(
+                get_tile_from_ascii(TilePos { x, y: pos.y - 1 }, map_size),
+                Tile::Stalk { .. }
+            ) {
+                pos.y -= 1;
+                plant_tiles.push(tile_storage.get(&pos).unwrap());
+            }
+            plant_tiles.reverse();
+            plant_tiles.push(tile_storage.get(&egg_pos).unwrap());
+            commands.spawn((
+                Plant::fruiting(Faction::from_egg_style(style), plant_tiles, &settings),
+                OnGameScreen,
+            ));
+        }
+    }
+
+    commands.insert_resource(EggFactionPicker::default());
+    commands.insert_resource(PlantSpawner {
+        timer: Timer::new(Duration ::from_secs_f32(settings.egg_spawn_time_start), TimerMode::Repeating),
+        mode: settings.level.egg_spawn_mode.clone(),
+        strategy: settings.level.egg_spawn_mode.strategy(&settings),
+    });
+    commands.insert_resource(FallTimer(
+        Timer::from_seconds(settings.tile_fall_secs, TimerMode::Repeating)
     ));
 
     commands.spawn(OnGameScreen)
@@ -329,24 +459,12 @@ fn spawn_tile(commands: &mut Commands, position: TilePos, tile: Tile, map_ent: E
     let health = HealthByte(100);
 
     match tile {
-        Tile::Dirt { topsoil: true, .. } => commands.spawn((tbundle, Topsoil, tile, health)),
-        Tile::Stalk { .. } => commands.spawn((
-            tbundle,
-            Plant {
-                ptype: Faction::Green,
-                status: PlantStatus::Growing,
-            },
-            tile,
-            health,
-        )),
+        Tile::Dirt { topsoil: true, .. } => commands.spawn((tbundle, Topsoil, Fertility::default(), tile, health)),
+        Tile::Dirt { .. } => commands.spawn((tbundle, Fertility::default(), tile, health)),
         Tile::Egg { style } => {
             commands.spawn((
                 tbundle,
-                Egg { faction: match style {
-                    0 => Faction::Red,
-                    1 => Faction::Blue,
-                    _ => Faction::Green
-                }},
+                Egg { faction: Faction::from_egg_style(style) },
                 tile,
                 health))
         },
@@ -392,10 +510,10 @@ fn get_tile_from_ascii(pos: TilePos, size: TilemapSize) -> Tile {
             topsoil: false,
         },
         b'X' => Tile::Rock { style: 0 },
-        b'1' => Tile::Egg { style: 0 },
-        b'2' => Tile::Egg { style: 1 },
-        b'a' => Tile::Poo { style: 0 },
-        b'b' => Tile::Poo { style: 1 },
+        b'1' => Tile::Egg { style: Faction::Red.egg_style() },
+        b'2' => Tile::Egg { style: Faction::Blue.egg_style() },
+        b'a' => Tile::Poo { style: Faction::Red.egg_style() },
+        b'b' => Tile::Poo { style: Faction::Blue.egg_style() },
         b't' => Tile::Stalk { style: 0 },
         b'L' => Tile::Leaves { style: 1 },
         b'.' => Tile::Air,
@@ -418,7 +536,7 @@ fn highlight_tile(
         Without<Cursor>,
     >,
     mut tile_q: Query<(&mut Tile, &mut HealthByte)>,
-    inv: Res<Inventory>, // TODO: not using this anymore
+    mut inv: ResMut<Inventory>,
     assets: Res<AssetCol>,
     audio: Res<Audio>,
     game_data: Res<GameData>
@@ -456,9 +574,12 @@ fn highlight_tile(
             pointer.set_active_item(*tile);
 
             if pointer.is_down && tile.texture() != pointer.tile.texture() {
-                let (did_draw, _dirts) = draw_tile(&pointer.tile, &tile, inv.dirt);
+                let cur_dirts = if inv.unlimited { u32::MAX } else { inv.dirt };
+                let (did_draw, dirts) = draw_tile(&pointer.tile, &tile, cur_dirts);
                 if did_draw {
-                    // inv.dirt = dirts; TODO: not using inventory system anymore
+                    if !inv.unlimited {
+                        inv.dirt = dirts;
+                    }
                     *tile = pointer.tile;
                     health.0 = 100; // Reset tile Health
 
@@ -482,11 +603,14 @@ fn highlight_tile(
 fn update_tile(
     mut commands: Commands,
     mut tilemap: Query<(&TileStorage, &TilemapSize)>,
-    mut tile_query: Query<(Entity, &mut TileTextureIndex, &Tile, &TilePos), Or<(Added<Tile>, Changed<Tile>)>>,
+    mut tile_query: Query<
+        (Entity, &mut TileTextureIndex, &mut TileColor, &Tile, &TilePos, Option<&Fertility>),
+        Or<(Added<Tile>, Changed<Tile>)>
+    >,
 ) {
     let (_storage, map_size) = tilemap.single_mut();
 
-    for (ent, mut tile_texture, tile, pos) in &mut tile_query {
+    for (ent, mut tile_texture, mut color, tile, pos, fertility) in &mut tile_query {
         tile_texture.0 = tile.texture();
         match tile {
             Tile::Dirt { topsoil: false, .. } => {
@@ -495,8 +619,15 @@ fn update_tile(
             Tile::Dirt { topsoil: true, .. } => {
                 commands.entity(ent).insert(Topsoil);
             }
-            _ => (),
+            _ => {
+                // Dug out: whatever goes here next is loose
+                commands.entity(ent).remove::<(Anchored, Fertility)>();
+                *color = TileColor::default();
+            }
         };
+        if matches!(tile, Tile::Dirt { .. }) && fertility.is_none() {
+            commands.entity(ent).insert(Fertility::default());
+        }
 
         // TODO: autotile
          let _n = Neighbors::get_square_neighboring_positions(&pos, map_size, true);
@@ -521,7 +652,11 @@ pub fn draw_tile(
         }
         // Draw air over dirt
         (Tile::Air, Tile::Dirt {..}) => {
-            dirts += 1;
+            dirts = dirts.saturating_add(1);
+            did_draw = true;
+        }
+        // Fertilizer is free to place and clear
+        (Tile::Poo { .. }, Tile::Air) | (Tile::Air, Tile::Poo { .. }) => {
             did_draw = true;
         }
         // No drawing
@@ -533,37 +668,68 @@ pub fn draw_tile(
 }
 
 
+/// Leaves anywhere above a tile block the light.
+fn is_shaded(pos: &TilePos, storage: &TileStorage, map_size: &TilemapSize, tiles: &Query<&Tile>) -> bool {
+    (pos.y + 1..map_size.y).any(|y| {
+        storage.get(&TilePos { x: pos.x, y })
+            .and_then(|ent| tiles.get(ent).ok())
+            .is_some_and(|tile| matches!(tile, Tile::Leaves { .. }))
+    })
+}
+
 fn spawn_plant(
     mut commands: Commands,
     mut tilemap_query: Query<(&TileStorage, &TilemapSize)>,
-    topsoil: Query<(Entity, &TilePos), With<Topsoil>>,
+    mut topsoil: Query<(Entity, &TilePos, &mut Fertility), With<Topsoil>>,
     tile_query: Query<&Tile>,
-    time: Res<Time>,
+    beez: Query<&RumbleBee>,
+    fixed_time: Res<FixedTime>,
     mut plant_spawner: ResMut<PlantSpawner>,
-    mut game_data: ResMut<GameData>
+    mut egg_factions: ResMut<EggFactionPicker>,
+    mut game_data: ResMut<GameData>,
+    settings: Res<GameSettings>
 ) {
     if game_data.game_over {
         return;
     }
 
-    plant_spawner.tick(time.delta());
-    if !plant_spawner.finished() {
+    plant_spawner.timer.tick(fixed_time.period);
+    if !plant_spawner.timer.finished() {
         return;
     }
 
-    // Speed up egg spawner
-    let speed = (game_data.egg_spawn_time -
-        ((EGG_SPAWN_TIME_START - EGG_SPAWN_TIME_END) * EGG_SPAWN_SPEEDUP_PERC))
-        .max(EGG_SPAWN_TIME_END);
-    plant_spawner.set_duration(Duration ::from_secs_f32(speed));
+    let bee_counts: Vec<(Faction, usize)> = settings.level.factions
+        .iter()
+        .copied()
+        .filter(|faction| !game_data.eliminated.contains(faction))
+        .map(|faction| (faction, beez.iter().filter(|bee| bee.faction == faction).count()))
+        .collect();
+    let ctx = SpawnContext {
+        settings: &settings,
+        bee_counts: &bee_counts,
+    };
+
+    // The mode can change when settings.ron is reloaded
+    if plant_spawner.mode != settings.level.egg_spawn_mode {
+        plant_spawner.mode = settings.level.egg_spawn_mode.clone();
+        plant_spawner.strategy = settings.level.egg_spawn_mode.strategy(&settings);
+    }
+
+    // When's the next one?
+    let speed = plant_spawner.strategy.next_interval(&ctx);
+    plant_spawner.timer.set_duration(Duration ::from_secs_f32(speed));
     game_data.egg_spawn_time = speed;
-    screen_print!(sec: 5.0, "Egg spawn speed: {:?}", speed);
+    screen_print!(sec: 5.0, "Egg spawn speed: {:?} ({:?})", speed, plant_spawner.mode);
+    let strategy_faction = plant_spawner.strategy.faction(&ctx);
 
     let (tile_storage, map_size) = tilemap_query.single_mut();
 
-    let mut possible_plants: Vec<(Entity, Vec<Entity>)> = vec![];
+    let mut possible_plants: Vec<(Entity, Vec<Entity>, Option<(Entity, Faction)>, f32)> = vec![];
 
-    for (topsoil_ent, topsoil_pos) in &topsoil {
+    for (topsoil_ent, topsoil_pos, fertility) in &topsoil {
+        if is_shaded(topsoil_pos, tile_storage, map_size, &tile_query) {
+            continue;
+        }
         let mut pos = *topsoil_pos;
         let mut plant_stack: Vec<Entity> = vec![];
         let mut rng = rand::thread_rng();
@@ -586,42 +752,68 @@ fn spawn_plant(
             }
         }
         if !plant_stack.is_empty() {
-            possible_plants.push((topsoil_ent, plant_stack));
+            let poo = fertilizer::fertilizer_near(topsoil_pos, tile_storage, map_size, &tile_query);
+            possible_plants.push((topsoil_ent, plant_stack, poo, fertility.0.max(0.0)));
         }
     }
 
-    if let Some((soil_ent, plant_stack)) = possible_plants.choose(&mut rand::thread_rng()) {
-        commands.entity(*soil_ent).insert(Tile::Dirt {
-            topsoil: false,
-            style: 5,
-        });
-        // Add stalks and egg
-        let egg_spot = plant_stack.len() - 1;
-        let mut i = 0;
-        let faction = Faction::random();
-
-        for plant_ent in plant_stack {
-            if i == egg_spot {
-                //let mut rng = rand::thread_rng();
-                //let is_blue = rng.gen_bool(0.5);
-                commands.entity(*plant_ent).insert((
-                    Egg { faction },
-                    Tile::Egg { style: match faction {
-                        Faction::Red => 0,
-                        Faction::Blue => 1,
-                        _ => 2
-                    }}
-                ));
-            } else {
-                commands.entity(*plant_ent).insert((
-                    Plant {
-                        ptype: faction,
-                        status: PlantStatus::Growing,
-                    },
-                    Tile::Stalk { style: 0 }
-                ));
-            }
-            i+=1;
+    let mut rng = rand::thread_rng();
+    // Tired soil is less likely to grow anything
+    let chosen = possible_plants.choose_weighted(&mut rng, |(_, _, poo, fertility)| {
+        if poo.is_some() { fertility * settings.fertilizer_spawn_weight.max(0.0) } else { *fertility }
+    });
+    if let Ok((soil_ent, plant_stack, poo, _)) = chosen {
+        if let Ok((_, _, mut fertility)) = topsoil.get_mut(*soil_ent) {
+            fertility.0 = (fertility.0 - settings.soil_fertility_drain).max(0.0);
+        }
+        let poo_faction = poo
+            .filter(|_| rng.gen_bool(settings.fertilizer_faction_bias.clamp(0.0, 1.0) as f64))
+            .map(|(_, faction)| faction);
+        let faction = poo_faction
+            .or(strategy_faction)
+            .unwrap_or_else(|| {
+                // Don't bother with eggs for knocked out factions
+                let alive: Vec<Faction> = settings.level.factions
+                    .iter()
+                    .copied()
+                    .filter(|faction| !game_data.eliminated.contains(faction))
+                    .collect();
+                egg_factions.next(&settings.level.egg_faction_mode, &alive)
+            });
+        screen_print!(sec: 5.0, "Egg faction mode: {:?}", settings.level.egg_faction_mode);
+
+        // Stalks grow up from the soil, then the egg on top
+        let mut plant = Plant::seed(faction, plant_stack.clone(), &settings);
+        if let Some((poo_ent, _)) = poo {
+            // The plant uses up the fertilizer
+            commands.entity(*poo_ent).insert(Tile::Air);
+            plant = plant.fertilized(&settings);
         }
+        commands.spawn((plant, OnGameScreen));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_egg_faction_picker() {
+        let factions = [Faction::Red, Faction::Blue];
+        let mut picker = EggFactionPicker::default();
+        let flip_flop: Vec<_> = (0..3).map(|_| picker.next(&EggFactionMode::FlipFlopNoGreen, &factions)).collect();
+        assert_eq!(flip_flop, [Faction::Red, Faction::Blue, Faction::Red]);
+
+        let mut picker = EggFactionPicker::default();
+        let ping_pong: Vec<_> = (0..6).map(|_| picker.next(&EggFactionMode::PingPong, &factions)).collect();
+        assert_eq!(ping_pong, [
+            Faction::Red, Faction::Blue, Faction::Green,
+            Faction::Blue, Faction::Red, Faction::Blue
+        ]);
+
+        let mut picker = EggFactionPicker::default();
+        let three_way = [Faction::Red, Faction::Blue, Faction::Yellow];
+        let flip_flop: Vec<_> = (0..4).map(|_| picker.next(&EggFactionMode::FlipFlopNoGreen, &three_way)).collect();
+        assert_eq!(flip_flop, [Faction::Red, Faction::Blue, Faction::Yellow, Faction::Red]);
     }
 }
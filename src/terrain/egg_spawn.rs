@@ -0,0 +1,104 @@
+//! How often eggs spawn.
+//!
+//! Each [`EggSpawnMode`] makes an [`EggSpawnStrategy`]. To add a mode, add a
+//! variant and a strategy for it.
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::settings::GameSettings;
+use crate::terrain::Faction;
+
+// Some egg-spawning-timing
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub enum EggSpawnMode {
+    Constant,
+    Random, // Jittered around the start time
+    #[default]
+    SpeedUp,
+    BeeBased // Faster, and for the losing side, when the factions are uneven
+}
+impl EggSpawnMode {
+    #[must_use]
+    pub fn strategy(&self, settings: &GameSettings) -> Box<dyn EggSpawnStrategy> {
+        match self {
+            Self::Constant => Box::new(Constant),
+            Self::Random => Box::new(Jittered),
+            Self::SpeedUp => Box::new(SpeedUp { interval: settings.egg_spawn_time_start }),
+            Self::BeeBased => Box::new(BeeBased),
+        }
+    }
+}
+
+/// What a strategy gets to look at when an egg spawns.
+pub struct SpawnContext<'a> {
+    pub settings: &'a GameSettings,
+    /// Living bees per faction.
+    pub bee_counts: &'a [(Faction, usize)],
+}
+
+pub trait EggSpawnStrategy: Send + Sync {
+    /// Seconds until the next egg.
+    fn next_interval(&mut self, ctx: &SpawnContext) -> f32;
+    /// Choose the faction of the egg, instead of the level's faction mode.
+    fn faction(&self, _ctx: &SpawnContext) -> Option<Faction> {
+        None
+    }
+}
+
+struct Constant;
+impl EggSpawnStrategy for Constant {
+    fn next_interval(&mut self, ctx: &SpawnContext) -> f32 {
+        ctx.settings.egg_spawn_time_start
+    }
+}
+
+struct Jittered;
+impl EggSpawnStrategy for Jittered {
+    fn next_interval(&mut self, ctx: &SpawnContext) -> f32 {
+        let jitter = ctx.settings.egg_spawn_jitter.clamp(0.0, 0.95);
+        ctx.settings.egg_spawn_time_start * rand::thread_rng().gen_range(1.0 - jitter..=1.0 + jitter)
+    }
+}
+
+/// Get a bit faster every egg, down to the end time.
+struct SpeedUp {
+    interval: f32,
+}
+impl EggSpawnStrategy for SpeedUp {
+    fn next_interval(&mut self, ctx: &SpawnContext) -> f32 {
+        let s = ctx.settings;
+        self.interval = (self.interval -
+            ((s.egg_spawn_time_start - s.egg_spawn_time_end) * s.egg_spawn_speedup_perc))
+            .max(s.egg_spawn_time_end);
+        self.interval
+    }
+}
+
+/// Start time when the factions are even, end time when one is nearly wiped out.
+struct BeeBased;
+impl BeeBased {
+    /// Below this balance the losing side gets all the eggs.
+    const HELP_BALANCE: f32 = 0.75;
+
+    /// Weakest faction and its size relative to the strongest (0..1).
+    fn balance(ctx: &SpawnContext) -> Option<(Faction, f32)> {
+        let (weakest, min) = *ctx.bee_counts.iter().min_by_key(|(_, n)| *n)?;
+        let max = ctx.bee_counts.iter().map(|(_, n)| *n).max()?;
+        if max == 0 {
+            return None;
+        }
+        Some((weakest, min as f32 / max as f32))
+    }
+}
+impl EggSpawnStrategy for BeeBased {
+    fn next_interval(&mut self, ctx: &SpawnContext) -> f32 {
+        let s = ctx.settings;
+        let balance = Self::balance(ctx).map_or(1.0, |(_, b)| b);
+        s.egg_spawn_time_end + (s.egg_spawn_time_start - s.egg_spawn_time_end) * balance
+    }
+    fn faction(&self, ctx: &SpawnContext) -> Option<Faction> {
+        Self::balance(ctx)
+            .filter(|(_, balance)| *balance < Self::HELP_BALANCE)
+            .map(|(faction, _)| faction)
+    }
+}
@@ -0,0 +1,151 @@
+//! Falling-sand step for loose tiles, turned on with the `falling_tiles`
+//! level rule.
+//!
+//! Painted dirt, poo and eggs fall when there's air under them, and dirt
+//! slides off the sides of piles. Terrain from the level map is anchored,
+//! so it stays put. Tiles move by changing their `Tile`, so the navmesh and
+//! paths update like any other tile change.
+use crate::game::{Health, HealthByte};
+use crate::prelude::*;
+use crate::rumblebees::RumbleBee;
+use crate::settings::GameSettings;
+use crate::simulation::SimPosition;
+use crate::terrain::{Egg, GAP_LEFT, Tile, px_to_tilepos, tilepos_to_px};
+use crate::terrain::fertilizer::PooRot;
+use crate::terrain::soil::Fertility;
+use rand::Rng;
+
+/// Level terrain that doesn't fall.
+#[derive(Component)]
+pub struct Anchored;
+
+#[derive(Resource)]
+pub struct FallTimer(pub Timer);
+
+type LooseTiles<'w, 's> = Query<'w, 's, (
+    &'static mut Tile,
+    &'static mut HealthByte,
+    Option<&'static Egg>,
+    Option<&'static Anchored>,
+    Option<&'static Fertility>,
+    Option<&'static PooRot>,
+)>;
+
+fn is_loose(tile: Tile) -> bool {
+    matches!(tile, Tile::Dirt { .. } | Tile::Poo { .. } | Tile::Egg { .. })
+}
+
+fn tile_at(tiles: &LooseTiles, storage: &TileStorage, pos: TilePos) -> Option<Tile> {
+    storage
+        .get(&pos)
+        .and_then(|ent| tiles.get(ent).ok())
+        .map(|(tile, ..)| *tile)
+}
+
+pub fn fall_loose_tiles(
+    mut commands: Commands,
+    mut timer: ResMut<FallTimer>,
+    tilemap: Query<(&TileStorage, &TilemapSize, &TilemapGridSize)>,
+    mut tiles: LooseTiles,
+    mut beez: Query<(Entity, &mut SimPosition, &mut Health), With<RumbleBee>>,
+    fixed_time: Res<FixedTime>,
+    settings: Res<GameSettings>,
+) {
+    if !settings.level.falling_tiles || !timer.0.tick(fixed_time.period).just_finished() {
+        return;
+    }
+    let (storage, map_size, grid_size) = tilemap.single();
+    let mut rng = rand::thread_rng();
+    let is_air = |tiles: &LooseTiles, x: u32, y: u32| {
+        matches!(tile_at(tiles, storage, TilePos { x, y }), Some(Tile::Air))
+    };
+
+    // Bottom up, so each tile moves at most once a step
+    for y in 1..map_size.y {
+        for x in 0..map_size.x {
+            let from = TilePos { x, y };
+            let Some(from_ent) = storage.get(&from) else { continue };
+            let Ok((tile, health, egg, anchored, fertility, rot)) = tiles.get(from_ent) else {
+                continue;
+            };
+            if anchored.is_some() || !is_loose(*tile) {
+                continue;
+            }
+            let (tile, health) = (*tile, health.0);
+            let (faction, fertility) = (egg.map(|egg| egg.faction), fertility.copied());
+            let rot = rot.cloned();
+
+            let to = if is_air(&tiles, x, y - 1) {
+                TilePos { x, y: y - 1 }
+            } else if matches!(tile, Tile::Dirt { .. }) {
+                // Slide down the side of a pile
+                let mut sides = vec![];
+                if x > 0 && is_air(&tiles, x - 1, y) && is_air(&tiles, x - 1, y - 1) {
+                    sides.push(x - 1);
+                }
+                if x + 1 < map_size.x && is_air(&tiles, x + 1, y) && is_air(&tiles, x + 1, y - 1) {
+                    sides.push(x + 1);
+                }
+                if sides.is_empty() {
+                    continue;
+                }
+                TilePos { x: sides[rng.gen_range(0..sides.len())], y: y - 1 }
+            } else {
+                continue;
+            };
+            let Some(to_ent) = storage.get(&to) else { continue };
+
+            if let Ok((mut from_tile, ..)) = tiles.get_mut(from_ent) {
+                *from_tile = Tile::Air;
+            }
+            if let Ok((mut to_tile, mut to_health, ..)) = tiles.get_mut(to_ent) {
+                *to_tile = tile;
+                to_health.0 = health;
+            }
+            commands.entity(to_ent).remove::<Anchored>();
+            if let Some(fertility) = fertility {
+                commands.entity(to_ent).insert(fertility);
+            }
+            if let Some(rot) = rot {
+                // rot_poo tidies up the one left behind
+                commands.entity(to_ent).insert(rot);
+            }
+            if let Some(faction) = faction {
+                commands.entity(from_ent).remove::<Egg>();
+                commands.entity(to_ent).insert(Egg { faction });
+            }
+
+            if Tile::is_solid(tile) {
+                squash_bees(&mut commands, &to, &mut beez, &tiles, storage, grid_size, &settings);
+            }
+        }
+    }
+}
+
+/// Hurt any bees where a tile landed, and push them out beside it.
+fn squash_bees(
+    commands: &mut Commands,
+    landed: &TilePos,
+    beez: &mut Query<(Entity, &mut SimPosition, &mut Health), With<RumbleBee>>,
+    tiles: &LooseTiles,
+    storage: &TileStorage,
+    grid_size: &TilemapGridSize,
+    settings: &GameSettings,
+) {
+    let free_side = [landed.x.checked_sub(1), landed.x.checked_add(1)]
+        .into_iter()
+        .flatten()
+        .map(|x| TilePos { x, y: landed.y })
+        .find(|pos| tile_at(tiles, storage, *pos).is_some_and(|tile| !Tile::is_solid(tile)));
+
+    for (ent, mut sim, mut health) in beez.iter_mut() {
+        if px_to_tilepos(sim.pos - Vec2::new(GAP_LEFT, 0.0), grid_size) != *landed {
+            continue;
+        }
+        health.0 -= settings.falling_tile_damage;
+        if let Some(side) = free_side {
+            sim.pos = tilepos_to_px(&side, grid_size);
+        }
+        commands.entity(ent).remove::<Pathfinding>();
+    }
+}
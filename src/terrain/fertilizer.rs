@@ -0,0 +1,132 @@
+//! Bee poo. Bees drop it as they fly, and it falls until it lands on
+//! something solid. Settled poo fertilizes the topsoil around it: plants
+//! there are picked more often, grow faster, and tend to fruit eggs of
+//! the poo's colour. Bee poo that isn't used up crumbles away after a while,
+//! so it doesn't pile up on rock and wall bees in.
+use crate::game::OnGameScreen;
+use crate::prelude::*;
+use crate::rumblebees::{Inactive, RumbleBee};
+use crate::settings::GameSettings;
+use crate::simulation::{SimPosition, sim_delta};
+use crate::terrain::{Faction, GAP_LEFT, TILE_SIZE, Tile, px_to_tilepos, tilepos_to_px};
+use crate::{AssetCol, Layers};
+use bevy_ecs_tilemap::helpers::square_grid::neighbors::Neighbors;
+use rand::Rng;
+
+/// Settled bee poo, crumbling away. Painted poo doesn't get one.
+#[derive(Component, Debug, Clone)]
+pub struct PooRot(pub Timer);
+
+/// Poo on its way down.
+#[derive(Component, Debug)]
+pub struct FallingPoo {
+    pub faction: Faction,
+}
+
+pub fn drop_poo(
+    mut commands: Commands,
+    beez: Query<(&RumbleBee, &SimPosition), Without<Inactive>>,
+    assets: Res<AssetCol>,
+    fixed_time: Res<FixedTime>,
+    settings: Res<GameSettings>,
+) {
+    let mut rng = rand::thread_rng();
+    let chance = settings.poo_chance_per_sec * sim_delta(&fixed_time);
+    for (bee, sim) in &beez {
+        if !rng.gen_bool(chance.clamp(0.0, 1.0) as f64) {
+            continue;
+        }
+        commands.spawn((
+            SpriteSheetBundle {
+                texture_atlas: assets.tiles.clone(),
+                sprite: TextureAtlasSprite {
+                    index: Tile::Poo { style: bee.faction.egg_style() }.texture() as usize,
+                    custom_size: Some(Vec2::splat(TILE_SIZE * 0.5)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(sim.pos.x, sim.pos.y, Layers::MIDGROUND - 0.5),
+                ..default()
+            },
+            SimPosition::new(sim.pos),
+            FallingPoo { faction: bee.faction },
+            OnGameScreen,
+        ));
+    }
+}
+
+/// Move falling poo down, and turn it into a tile when it lands.
+pub fn settle_poo(
+    mut commands: Commands,
+    mut falling: Query<(Entity, &FallingPoo, &mut SimPosition)>,
+    mut tiles: Query<&mut Tile>,
+    tilemap: Query<(&TileStorage, &TilemapGridSize)>,
+    fixed_time: Res<FixedTime>,
+    settings: Res<GameSettings>,
+) {
+    let (storage, grid_size) = tilemap.single();
+    for (ent, poo, mut sim) in &mut falling {
+        sim.pos.y -= settings.poo_fall_speed * sim_delta(&fixed_time);
+
+        let tile_pos = px_to_tilepos(sim.pos - Vec2::new(GAP_LEFT, 0.0), grid_size);
+        let Some(tile_ent) = storage.get(&tile_pos) else {
+            // Fell off the map
+            commands.entity(ent).despawn();
+            continue;
+        };
+        let landed = match tile_pos.y.checked_sub(1) {
+            Some(y) => storage
+                .get(&TilePos { x: tile_pos.x, y })
+                .and_then(|below| tiles.get(below).ok())
+                .is_some_and(|below| Tile::is_solid(*below)),
+            None => true,
+        };
+        if !landed || sim.pos.y > tilepos_to_px(&tile_pos, grid_size).y {
+            continue;
+        }
+
+        commands.entity(ent).despawn();
+        if let Ok(mut tile) = tiles.get_mut(tile_ent) {
+            // Landing in leaves or an egg squashes it
+            if matches!(*tile, Tile::Air) {
+                *tile = Tile::Poo { style: poo.faction.egg_style() };
+                commands
+                    .entity(tile_ent)
+                    .insert(PooRot(Timer::from_seconds(settings.poo_rot_secs, TimerMode::Once)));
+            }
+        }
+    }
+}
+
+pub fn rot_poo(
+    mut commands: Commands,
+    mut rotting: Query<(Entity, &mut PooRot, &mut Tile)>,
+    fixed_time: Res<FixedTime>,
+) {
+    for (ent, mut rot, mut tile) in &mut rotting {
+        // Used up by a plant, dug out, or fallen away
+        if !matches!(*tile, Tile::Poo { .. }) {
+            commands.entity(ent).remove::<PooRot>();
+            continue;
+        }
+        if rot.0.tick(fixed_time.period).finished() {
+            *tile = Tile::Air;
+            commands.entity(ent).remove::<PooRot>();
+        }
+    }
+}
+
+/// The poo tile fertilizing a topsoil tile, if any is beside it.
+pub fn fertilizer_near(
+    pos: &TilePos,
+    storage: &TileStorage,
+    map_size: &TilemapSize,
+    tiles: &Query<&Tile>,
+) -> Option<(Entity, Faction)> {
+    Neighbors::get_square_neighboring_positions(pos, map_size, true)
+        .iter()
+        .filter_map(|n| storage.get(n))
+        .find_map(|ent| match tiles.get(ent) {
+            Ok(Tile::Poo { style }) => Some((ent, Faction::from_egg_style(*style))),
+            _ => None,
+        })
+}
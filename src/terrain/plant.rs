@@ -0,0 +1,203 @@
+//! Plants grow a stalk up from topsoil one tile at a time, fruit an egg on
+//! top, then wilt and decay back to air.
+//!
+//! Fruiting plants sprout leaves beside the top of the stalk. Bees are slow
+//! going through leaves, and topsoil under leaves is too shady for plants.
+use bevy_ecs_tilemap::helpers::square_grid::neighbors::Neighbors;
+
+use crate::prelude::*;
+use crate::settings::GameSettings;
+use crate::terrain::{Egg, Faction, Tile};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlantStatus {
+    Dead,
+    Growing,
+    Fruiting,
+}
+
+#[derive(Component, Debug)]
+pub struct Plant {
+    pub ptype: Faction,
+    pub status: PlantStatus,
+    /// Tiles from the bottom up. The top one is where the egg goes.
+    pub tiles: Vec<Entity>,
+    pub leaves: Vec<Entity>,
+    sprouted_leaves: bool,
+    grown: usize,
+    timer: Timer,
+}
+impl Plant {
+    /// A new plant. Nothing is grown yet.
+    #[must_use]
+    pub fn seed(ptype: Faction, tiles: Vec<Entity>, settings: &GameSettings) -> Self {
+        Self {
+            ptype,
+            status: PlantStatus::Growing,
+            tiles,
+            leaves: vec![],
+            sprouted_leaves: false,
+            grown: 0,
+            timer: Timer::from_seconds(settings.plant_grow_secs, TimerMode::Repeating),
+        }
+    }
+    /// Grow faster from fertilized soil.
+    #[must_use]
+    pub fn fertilized(mut self, settings: &GameSettings) -> Self {
+        let secs = settings.plant_grow_secs * settings.fertilizer_grow_scale;
+        self.timer = Timer::from_seconds(secs, TimerMode::Repeating);
+        self
+    }
+    /// A fully grown plant that already has its egg.
+    #[must_use]
+    pub fn fruiting(ptype: Faction, tiles: Vec<Entity>, settings: &GameSettings) -> Self {
+        Self {
+            ptype,
+            status: PlantStatus::Fruiting,
+            grown: tiles.len(),
+            tiles,
+            leaves: vec![],
+            sprouted_leaves: false,
+            timer: Timer::from_seconds(settings.plant_fruit_secs, TimerMode::Once),
+        }
+    }
+    #[must_use]
+    pub fn egg_tile(&self) -> Entity {
+        *self.tiles.last().unwrap()
+    }
+}
+
+pub fn grow_plants(
+    mut commands: Commands,
+    mut plants: Query<(Entity, &mut Plant)>,
+    mut tiles: Query<&mut Tile>,
+    positions: Query<&TilePos>,
+    tilemap: Query<(&TileStorage, &TilemapSize)>,
+    eggs: Query<(), With<Egg>>,
+    fixed_time: Res<FixedTime>,
+    settings: Res<GameSettings>,
+) {
+    let (storage, map_size) = tilemap.single();
+    for (ent, mut plant) in &mut plants {
+        match plant.status {
+            PlantStatus::Growing => {
+                if !plant.timer.tick(fixed_time.period).just_finished() {
+                    continue;
+                }
+                let next = plant.tiles[plant.grown];
+                if matches!(tiles.get(next), Ok(Tile::Air)) {
+                    plant.grown += 1;
+                    if plant.grown < plant.tiles.len() {
+                        if let Ok(mut tile) = tiles.get_mut(next) {
+                            *tile = Tile::Stalk { style: 0 };
+                        }
+                        continue;
+                    }
+                } else {
+                    // Something is in the way. Fruit early, or give up.
+                    let grown = plant.grown;
+                    plant.tiles.truncate(grown);
+                    if plant.tiles.is_empty() {
+                        commands.entity(ent).despawn();
+                        continue;
+                    }
+                }
+
+                // Reached the top: grow the egg
+                let faction = plant.ptype;
+                commands.entity(plant.egg_tile()).insert((
+                    Egg { faction },
+                    Tile::Egg { style: faction.egg_style() },
+                ));
+                plant.grown = plant.tiles.len();
+                plant.status = PlantStatus::Fruiting;
+                plant.timer = Timer::from_seconds(settings.plant_fruit_secs, TimerMode::Once);
+            }
+            PlantStatus::Fruiting => {
+                if !plant.sprouted_leaves {
+                    sprout_leaves(&mut plant, storage, map_size, &positions, &mut tiles);
+                }
+                let collected = !eggs.contains(plant.egg_tile());
+                let rotted = plant.timer.tick(fixed_time.period).finished();
+                if !collected && !rotted {
+                    continue;
+                }
+                if !collected {
+                    commands.entity(plant.egg_tile()).remove::<Egg>();
+                }
+                wilt(&plant, &mut tiles);
+                plant.status = PlantStatus::Dead;
+                plant.timer = Timer::from_seconds(settings.plant_decay_secs, TimerMode::Repeating);
+            }
+            PlantStatus::Dead => {
+                if !plant.timer.tick(fixed_time.period).just_finished() {
+                    continue;
+                }
+                // Leaves go first
+                if let Some(leaf) = plant.leaves.pop() {
+                    if let Ok(mut tile) = tiles.get_mut(leaf) {
+                        if matches!(*tile, Tile::Leaves { .. }) {
+                            *tile = Tile::Air;
+                        }
+                    }
+                    continue;
+                }
+                // Then the stalk, from the top down
+                let top_stalk = plant.tiles
+                    .iter()
+                    .rev()
+                    .find(|ent| matches!(tiles.get(**ent), Ok(Tile::Stalk { .. })))
+                    .copied();
+                match top_stalk.and_then(|ent| tiles.get_mut(ent).ok()) {
+                    Some(mut tile) => *tile = Tile::Air,
+                    None => commands.entity(ent).despawn(),
+                }
+            }
+        }
+    }
+}
+
+/// Grow leaves in the air beside the egg and the top of the stalk.
+fn sprout_leaves(
+    plant: &mut Plant,
+    storage: &TileStorage,
+    map_size: &TilemapSize,
+    positions: &Query<&TilePos>,
+    tiles: &mut Query<&mut Tile>,
+) {
+    plant.sprouted_leaves = true;
+    let mut leaves = vec![];
+    for ent in plant.tiles.iter().rev().take(2) {
+        let Ok(pos) = positions.get(*ent) else { continue };
+        let n = Neighbors::get_square_neighboring_positions(pos, map_size, false);
+        for side in [n.west, n.east].into_iter().flatten() {
+            let Some(leaf) = storage.get(&side) else { continue };
+            if let Ok(mut tile) = tiles.get_mut(leaf) {
+                if matches!(*tile, Tile::Air) {
+                    *tile = Tile::Leaves { style: 0 };
+                    leaves.push(leaf);
+                }
+            }
+        }
+    }
+    plant.leaves = leaves;
+}
+
+/// Turn the stalk into a wilted tree, and remove the egg.
+fn wilt(plant: &Plant, tiles: &mut Query<&mut Tile>) {
+    let (top, stalks) = plant.tiles.split_last().unwrap();
+    for (i, ent) in stalks.iter().enumerate() {
+        if let Ok(mut tile) = tiles.get_mut(*ent) {
+            if matches!(*tile, Tile::Stalk { .. }) {
+                *tile = Tile::Stalk { style: if i + 1 == stalks.len() { 2 } else { 1 } };
+            }
+        }
+    }
+    if let Ok(mut tile) = tiles.get_mut(*top) {
+        if matches!(*tile, Tile::Air | Tile::Egg { .. }) {
+            // A 1-high tree leaves a dead stalk (instead of air).
+            // Otherwise making 1-high would be dominant strat
+            *tile = if stalks.is_empty() { Tile::Stalk { style: 2 } } else { Tile::Air };
+        }
+    }
+}
@@ -0,0 +1,64 @@
+//! Soil fertility. Growing a plant drains the dirt it grows from, and the
+//! dirt slowly recovers, faster with fertilizer beside it. Tired dirt is
+//! drawn darker.
+use crate::game::HealthByte;
+use crate::prelude::*;
+use crate::settings::GameSettings;
+use crate::simulation::sim_delta;
+use crate::terrain::Tile;
+use crate::terrain::fertilizer::fertilizer_near;
+
+const EXHAUSTED_COLOR: Color = Color::rgb(0.45, 0.4, 0.35);
+
+/// 0..1 how well plants grow from a dirt tile.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Fertility(pub f32);
+impl Default for Fertility {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+pub fn regenerate_soil(
+    mut soil: Query<(&mut Fertility, &TilePos)>,
+    tiles: Query<&Tile>,
+    tilemap: Query<(&TileStorage, &TilemapSize)>,
+    fixed_time: Res<FixedTime>,
+    settings: Res<GameSettings>,
+) {
+    let (storage, map_size) = tilemap.single();
+    let dt = sim_delta(&fixed_time);
+    for (mut fertility, pos) in &mut soil {
+        if fertility.0 >= 1.0 {
+            continue;
+        }
+        let mut regen = settings.soil_regen_per_sec;
+        if fertilizer_near(pos, storage, map_size, &tiles).is_some() {
+            regen += settings.fertilizer_regen_per_sec;
+        }
+        fertility.0 = (fertility.0 + regen * dt).min(1.0);
+    }
+}
+
+/// The dirt's tint: darker when tired, and fading as it's dug out.
+pub fn show_fertility(
+    mut soil: Query<
+        (&Tile, &Fertility, &HealthByte, &mut TileColor),
+        Or<(Changed<Fertility>, Changed<HealthByte>)>,
+    >,
+) {
+    for (tile, fertility, health, mut color) in &mut soil {
+        // Just dug out. update_tile has reset the colour
+        if !matches!(tile, Tile::Dirt { .. }) {
+            continue;
+        }
+        let f = fertility.0.clamp(0.0, 1.0);
+        let [r, g, b, _] = EXHAUSTED_COLOR.as_rgba_f32();
+        color.0 = Color::rgba(
+            r + (1.0 - r) * f,
+            g + (1.0 - g) * f,
+            b + (1.0 - b) * f,
+            f32::from(health.0.min(100)) / 100.0,
+        );
+    }
+}
@@ -0,0 +1,216 @@
+//! Game speed: slow motion, fast forward and single stepping.
+//!
+//! The fixed simulation steps are driven by `Time`, so scaling the clock's
+//! relative speed scales every simulation timer consistently.
+use crate::game::OnGameScreen;
+use crate::pointer::Pointer;
+use crate::prelude::*;
+use crate::terrain::GAP_LEFT;
+use crate::{AssetCol, GameState, Layers};
+
+/// Speeds selectable with the number keys and the speed buttons.
+pub const GAME_SPEEDS: &[(f32, &str)] = &[
+    (0.5, "SLOW"),
+    (1.0, "1X"),
+    (2.0, "2X"),
+    (4.0, "4X"),
+];
+
+const BUTTON_HEIGHT: f32 = 30.0;
+const BUTTON_GAP: f32 = 6.0;
+const BUTTONS_BOTTOM: f32 = 90.0;
+
+const BUTTON_COLOR: Color = Color::rgb(0.2, 0.12, 0.08);
+const BUTTON_ACTIVE_COLOR: Color = Color::rgb(0.5, 0.3, 0.1);
+
+#[derive(Resource, Debug)]
+pub struct TimeScale {
+    pub speed: f32,
+    /// Time is paused, and only advances a simulation step at a time.
+    pub stepping: bool,
+    step_requested: bool,
+}
+impl TimeScale {
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed;
+        self.stepping = false;
+    }
+    /// Enter step mode, or advance one simulation step if already in it.
+    pub fn step(&mut self) {
+        if self.stepping {
+            self.step_requested = true;
+        } else {
+            self.stepping = true;
+        }
+    }
+}
+impl Default for TimeScale {
+    fn default() -> Self {
+        Self {
+            speed: 1.0,
+            stepping: false,
+            step_requested: false,
+        }
+    }
+}
+
+#[derive(Component)]
+struct SpeedButton(Option<f32>); // None is the step button
+
+pub struct TimeControlPlugin;
+impl Plugin for TimeControlPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<TimeScale>()
+            .add_systems(OnEnter(GameState::InGame), time_control_setup)
+            .add_systems(
+                Update,
+                (
+                    speed_hotkeys,
+                    speed_buttons,
+                    apply_time_scale.after(speed_hotkeys).after(speed_buttons),
+                    highlight_speed_buttons.after(apply_time_scale),
+                ).run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(OnExit(GameState::InGame), reset_time_scale);
+    }
+}
+
+fn button_y(i: usize) -> f32 {
+    BUTTONS_BOTTOM + i as f32 * (BUTTON_HEIGHT + BUTTON_GAP)
+}
+
+fn time_control_setup(
+    mut commands: Commands,
+    assets: Res<AssetCol>
+) {
+    let labels = GAME_SPEEDS
+        .iter()
+        .map(|(speed, label)| (Some(*speed), *label))
+        .chain(std::iter::once((None, "STEP")));
+
+    for (i, (speed, label)) in labels.enumerate() {
+        let y = button_y(i);
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: BUTTON_COLOR,
+                    custom_size: Some(Vec2::new(GAP_LEFT * 0.8, BUTTON_HEIGHT)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(GAP_LEFT / 2.0, y, Layers::UI),
+                ..default()
+            },
+            SpeedButton(speed),
+            OnGameScreen,
+        ));
+
+        commands.spawn((
+            TextBundle::from_section(
+                label,
+                TextStyle {
+                    font: assets.font.clone(),
+                    font_size: 20.0,
+                    color: Color::WHITE,
+                },
+            )
+            .with_text_alignment(TextAlignment::Center)
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(y - 10.0),
+                left: Val::Px(15.0),
+                ..default()
+            }),
+            OnGameScreen,
+        ));
+    }
+}
+
+fn speed_hotkeys(
+    keys: Res<Input<KeyCode>>,
+    mut scale: ResMut<TimeScale>,
+) {
+    const SPEED_KEYS: [KeyCode; 4] = [KeyCode::Key1, KeyCode::Key2, KeyCode::Key3, KeyCode::Key4];
+    for (key, (speed, _)) in SPEED_KEYS.iter().zip(GAME_SPEEDS) {
+        if keys.just_pressed(*key) {
+            scale.set_speed(*speed);
+        }
+    }
+    if keys.just_pressed(KeyCode::P) {
+        scale.stepping = !scale.stepping;
+    }
+    if keys.just_pressed(KeyCode::Period) {
+        scale.step();
+    }
+}
+
+fn speed_buttons(
+    pointer: Res<Pointer>,
+    buttons: Query<(&SpeedButton, &Transform)>,
+    mut scale: ResMut<TimeScale>,
+) {
+    if !pointer.released || pointer.pos.x > GAP_LEFT * 0.9 {
+        return;
+    }
+    for (button, transform) in &buttons {
+        if (pointer.pos.y - transform.translation.y).abs() > BUTTON_HEIGHT / 2.0 {
+            continue;
+        }
+        match button.0 {
+            Some(speed) => scale.set_speed(speed),
+            None => scale.step(),
+        }
+    }
+}
+
+fn apply_time_scale(
+    mut scale: ResMut<TimeScale>,
+    mut time: ResMut<Time>,
+    mut fixed_time: ResMut<FixedTime>,
+) {
+    if (time.relative_speed() - scale.speed).abs() > f32::EPSILON && !time.is_paused() {
+        time.set_relative_speed(scale.speed);
+        screen_print!(sec: 2.0, "Game speed: {}x", scale.speed);
+    }
+
+    if !scale.stepping {
+        if time.is_paused() {
+            time.set_relative_speed(scale.speed);
+            time.unpause();
+        }
+        return;
+    }
+
+    time.pause();
+    if scale.step_requested {
+        // Bank exactly one period so the next fixed update loop runs once.
+        scale.step_requested = false;
+        let period = fixed_time.period;
+        fixed_time.tick(period);
+    }
+}
+
+fn highlight_speed_buttons(
+    scale: Res<TimeScale>,
+    mut buttons: Query<(&SpeedButton, &mut Sprite)>,
+) {
+    if !scale.is_changed() {
+        return;
+    }
+    for (button, mut sprite) in &mut buttons {
+        let active = match button.0 {
+            Some(speed) => !scale.stepping && (speed - scale.speed).abs() < f32::EPSILON,
+            None => scale.stepping,
+        };
+        sprite.color = if active { BUTTON_ACTIVE_COLOR } else { BUTTON_COLOR };
+    }
+}
+
+fn reset_time_scale(
+    mut scale: ResMut<TimeScale>,
+    mut time: ResMut<Time>,
+) {
+    *scale = TimeScale::default();
+    time.set_relative_speed(1.0);
+    time.unpause();
+}